@@ -71,7 +71,7 @@ try {
 
     # Run tests
     Write-Host "`n=== Running tests ===" -ForegroundColor Cyan
-    cargo test --verbose
+    cargo test --all-features --verbose
     if ($LASTEXITCODE -ne 0) {
         $checksPassed = $false
     }
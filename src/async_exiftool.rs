@@ -0,0 +1,1024 @@
+use crate::builder::ExifToolBuilder;
+use crate::command::Command;
+use crate::error::ExifToolError;
+use crate::exiftool::{
+    check_stderr, json_batch_warnings, parse_json_output, parse_ready_marker, ready_marker,
+    split_batch_output, BatchResult, CommandOutput,
+};
+use crate::tag_value::{
+    deserialize_tag, parse_long_json, tag_value_args, Representation, TagValue,
+};
+use crate::warning::{stderr_warnings, WithWarnings};
+use crate::write::{originals_count, BackupPolicy, WriteReport};
+use log::{debug, warn};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command as ProcessCommand};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::time::{timeout_at, Instant};
+
+// How long to keep collecting stderr after the process terminated unexpectedly.
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Async counterpart of [`ExifTool`](crate::ExifTool), built on [`tokio::process`].
+///
+/// Requires the `tokio` cargo feature. All I/O with the persistent `exiftool` process goes
+/// through non-blocking pipes, so awaiting a command never blocks a runtime worker thread.
+///
+/// The constructors must be called from within a Tokio runtime, since they spawn a task
+/// that monitors the process's stderr.
+///
+/// Like the blocking client, a per-command timeout can be set with
+/// [`AsyncExifTool::set_timeout`]; a hung process is killed and replaced.
+///
+/// The `exiftool` process is killed when this struct is dropped. Use
+/// [`AsyncExifTool::close`] to shut it down gracefully instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use exiftool::{AsyncExifTool, ExifToolError};
+/// use std::path::Path;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), ExifToolError> {
+///     let mut et = AsyncExifTool::new()?;
+///
+///     let path = Path::new("image.jpg");
+///     let width: u32 = et.read_tag(path, "ImageWidth").await?;
+///     println!("Width: {}", width);
+///
+///     et.close().await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncExifTool {
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    stderr_receiver: UnboundedReceiver<String>,
+    child: Child,
+    builder: ExifToolBuilder,
+    timeout: Option<Duration>,
+    backup_policy: BackupPolicy,
+    command_id: u64,
+    pending: Option<Pending>,
+}
+
+/// How far the last command got, if its future was dropped before it completed.
+#[derive(Debug, Clone, Copy)]
+enum Pending {
+    /// The command was being written to stdin, so the process state is unknown.
+    Sending,
+    /// The command was sent; its stdout and stderr output are still unread.
+    Stdout(u64),
+    /// The command's stdout was read; its stderr output is still unread.
+    Stderr(u64),
+}
+
+impl AsyncExifTool {
+    /// Launches the `exiftool` process in stay-open mode using the default system path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExifToolError::ExifToolNotFound`] if the `exiftool` command cannot be found
+    /// or if the process fails to start.
+    /// Returns [`ExifToolError::Io`] if capturing the stdin/stdout/stderr pipes fails.
+    pub fn new() -> Result<Self, ExifToolError> {
+        Self::with_executable(Path::new("exiftool"))
+    }
+
+    /// Launches `exiftool` from a specific executable path in stay-open mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExifToolError::ExifToolNotFound`] if the specified `exiftool_path` does not exist,
+    /// is not executable, or if the process fails to start.
+    /// Returns [`ExifToolError::Io`] if capturing the stdin/stdout/stderr pipes fails.
+    pub fn with_executable(exiftool_path: &Path) -> Result<Self, ExifToolError> {
+        ExifToolBuilder::new()
+            .executable(exiftool_path)
+            .build_async()
+    }
+
+    /// Launches the stay-open process described by `builder`.
+    /// Internal helper function, used by [`ExifToolBuilder::build_async`].
+    pub(crate) fn spawn(builder: ExifToolBuilder) -> Result<Self, ExifToolError> {
+        let mut child = ProcessCommand::from(builder.command())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(ExifToolError::ExifToolNotFound)?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to capture stdin"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to capture stdout"))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to capture stderr"))?;
+
+        // Spawn a task to continuously read stderr
+        let (stderr_sender, stderr_receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if stderr_sender.send(line).is_err() {
+                    // Receiver has been dropped, exiftool process likely closing
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            stdin: BufWriter::new(stdin),
+            stdout: BufReader::new(stdout),
+            stderr_receiver,
+            child,
+            timeout: builder.initial_timeout(),
+            backup_policy: builder.initial_backup_policy(),
+            builder,
+            command_id: 0,
+            pending: None,
+        })
+    }
+
+    /// Returns the timeout applied to every command, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets a timeout applied to every command executed by this instance.
+    ///
+    /// See [`ExifTool::set_timeout`](crate::ExifTool::set_timeout). Prefer this over wrapping
+    /// calls in [`tokio::time::timeout`]: a cancelled command keeps running, and the next call
+    /// has to wait for its output (or restart the process) before sending its own command.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the backup policy applied to writes.
+    pub fn backup_policy(&self) -> BackupPolicy {
+        self.backup_policy
+    }
+
+    /// Sets what happens to the original file when a write modifies it.
+    ///
+    /// See [`ExifTool::set_backup_policy`](crate::ExifTool::set_backup_policy).
+    pub fn set_backup_policy(&mut self, policy: BackupPolicy) {
+        self.backup_policy = policy;
+    }
+
+    /// Gracefully shuts down the `exiftool` process and waits for it to exit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExifToolError::Io`] if writing the shutdown commands or waiting fails.
+    pub async fn close(mut self) -> Result<(), ExifToolError> {
+        self.stdin
+            .write_all(b"-stay_open\nFalse\n-execute\n")
+            .await?;
+        self.stdin.flush().await?;
+        let status = self.child.wait().await?;
+        debug!("Exiftool process exited with status: {}", status);
+        Ok(())
+    }
+
+    /// Executes a command with the provided arguments and returns the raw byte output.
+    ///
+    /// Async equivalent of [`ExifTool::execute_raw`](crate::ExifTool::execute_raw).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn execute_raw(&mut self, args: &[&str]) -> Result<Vec<u8>, ExifToolError> {
+        self.execute_raw_with_timeout(args, self.timeout).await
+    }
+
+    /// Like [`AsyncExifTool::execute_raw`], but with a timeout for this call only.
+    ///
+    /// Async equivalent of
+    /// [`ExifTool::execute_raw_with_timeout`](crate::ExifTool::execute_raw_with_timeout).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn execute_raw_with_timeout(
+        &mut self,
+        args: &[&str],
+        timeout: Option<Duration>,
+    ) -> Result<Vec<u8>, ExifToolError> {
+        let output = self.execute_command(args, timeout).await?;
+        check_stderr(args, &output.stderr)?;
+
+        Ok(output.stdout)
+    }
+
+    /// Executes a command and returns its stdout together with all of its stderr lines.
+    ///
+    /// Async equivalent of
+    /// [`ExifTool::execute_with_stderr`](crate::ExifTool::execute_with_stderr).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn execute_with_stderr(
+        &mut self,
+        args: &[&str],
+    ) -> Result<CommandOutput, ExifToolError> {
+        self.execute_command(args, self.timeout).await
+    }
+
+    /// Executes a command and also returns the warnings `exiftool` printed while running it.
+    ///
+    /// Async equivalent of
+    /// [`ExifTool::execute_with_diagnostics`](crate::ExifTool::execute_with_diagnostics).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn execute_with_diagnostics(
+        &mut self,
+        args: &[&str],
+    ) -> Result<WithWarnings<Vec<u8>>, ExifToolError> {
+        let output = self.execute_command(args, self.timeout).await?;
+        check_stderr(args, &output.stderr)?;
+        Ok(WithWarnings {
+            value: output.stdout,
+            warnings: stderr_warnings(args, &output.stderr),
+        })
+    }
+
+    /// Sends one command to the process and collects its complete stdout and stderr output.
+    async fn execute_command(
+        &mut self,
+        args: &[&str],
+        timeout: Option<Duration>,
+    ) -> Result<CommandOutput, ExifToolError> {
+        let start_time = Instant::now();
+        let deadline = timeout.map(|t| start_time + t);
+        if let Some(pending) = self.pending {
+            self.finish_cancelled(pending, deadline).await;
+        }
+        self.command_id += 1;
+        let command_id = self.command_id;
+
+        // Same framing as the blocking client: `-echo4` ends this command's stderr output,
+        // and the numbered execute ends stdout output with `{ready<N>}`.
+        let mut command = String::new();
+        for arg in args {
+            command.push_str(arg);
+            command.push('\n');
+        }
+        command.push_str(&format!(
+            "-echo4\n{}\n-execute{}\n",
+            ready_marker(command_id),
+            command_id
+        ));
+        self.pending = Some(Pending::Sending);
+        self.stdin.write_all(command.as_bytes()).await?;
+        self.stdin.flush().await?;
+        self.pending = Some(Pending::Stdout(command_id));
+
+        let output = match deadline {
+            Some(deadline) => {
+                match timeout_at(deadline, self.read_command_output(command_id)).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        // The process is stuck on this command, replace it so we remain usable.
+                        if let Err(e) = self.restart() {
+                            warn!("Failed to restart exiftool after a timeout: {}", e);
+                        }
+                        return Err(ExifToolError::Timeout {
+                            command_args: args.join(" "),
+                            elapsed: start_time.elapsed(),
+                        });
+                    }
+                }
+            }
+            None => self.read_command_output(command_id).await,
+        };
+        output.map(|(stdout, stderr)| CommandOutput { stdout, stderr })
+    }
+
+    /// Reads and discards the remaining output of a command whose future was dropped, so it
+    /// can't be returned as the output of the next command. Restarts the process if that isn't
+    /// possible before `deadline`.
+    async fn finish_cancelled(&mut self, pending: Pending, deadline: Option<Instant>) {
+        debug!("Finishing cancelled command: {:?}", pending);
+        let drain = async {
+            match pending {
+                Pending::Sending => Err(ExifToolError::ProcessTerminated),
+                Pending::Stdout(id) => self.read_command_output(id).await.map(|_| ()),
+                Pending::Stderr(id) => self.read_stderr_until_ready(id).await.map(|_| ()),
+            }
+        };
+        let drained = match deadline {
+            Some(deadline) => {
+                timeout_at(deadline, drain)
+                    .await
+                    .unwrap_or(Err(ExifToolError::Timeout {
+                        command_args: String::new(),
+                        elapsed: Duration::ZERO,
+                    }))
+            }
+            None => drain.await,
+        };
+        if drained.is_err() {
+            if let Err(e) = self.restart() {
+                warn!(
+                    "Failed to restart exiftool after a cancelled command: {}",
+                    e
+                );
+            }
+        }
+        self.pending = None;
+    }
+
+    /// Kills the current `exiftool` process and replaces it with a freshly spawned one.
+    fn restart(&mut self) -> Result<(), ExifToolError> {
+        if let Err(e) = self.child.start_kill() {
+            warn!("Failed to kill hung exiftool process: {}", e);
+        }
+        let mut fresh = Self::spawn(self.builder.clone())?;
+        fresh.timeout = self.timeout;
+        fresh.backup_policy = self.backup_policy;
+        *self = fresh;
+        Ok(())
+    }
+
+    /// Reads the stdout and stderr output of command `command_id`.
+    async fn read_command_output(
+        &mut self,
+        command_id: u64,
+    ) -> Result<(Vec<u8>, Vec<String>), ExifToolError> {
+        let stdout_bytes = self.read_response_until_ready(command_id).await?;
+        self.pending = Some(Pending::Stderr(command_id));
+        let stderr_lines = self.read_stderr_until_ready(command_id).await?;
+        self.pending = None;
+        Ok((stdout_bytes, stderr_lines))
+    }
+
+    /// Reads from stdout until the `{ready<N>}` marker of command `command_id` is found at
+    /// the end of the output.
+    async fn read_response_until_ready(
+        &mut self,
+        command_id: u64,
+    ) -> Result<Vec<u8>, ExifToolError> {
+        let mut buffer = Vec::new();
+        let marker = ready_marker(command_id);
+        let ready_markers = [format!("{}\n", marker), format!("{}\r\n", marker)];
+
+        loop {
+            let mut chunk = [0u8; 4096];
+            let bytes_read = self.stdout.read(&mut chunk).await?;
+            if bytes_read == 0 {
+                let stderr_lines = self.drain_remaining_stderr().await;
+                return if !stderr_lines.is_empty() {
+                    Err(ExifToolError::ExifToolProcess {
+                        std_err: stderr_lines.join("\n"),
+                        message: format!(
+                            "Process terminated unexpectedly. Stderr:\n{}",
+                            stderr_lines.join("\n")
+                        ),
+                        command_args: "<unknown - process terminated>".to_string(),
+                    })
+                } else {
+                    Err(ExifToolError::ProcessTerminated)
+                };
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+
+            for marker in &ready_markers {
+                if buffer.ends_with(marker.as_bytes()) {
+                    buffer.truncate(buffer.len() - marker.len());
+                    return Ok(buffer);
+                }
+            }
+        }
+    }
+
+    /// Collects the stderr lines of command `command_id`, up to its `-echo4` marker.
+    /// Lines followed by the marker of an older command are discarded.
+    async fn read_stderr_until_ready(
+        &mut self,
+        command_id: u64,
+    ) -> Result<Vec<String>, ExifToolError> {
+        let mut err_lines = Vec::new();
+        loop {
+            match self.stderr_receiver.recv().await {
+                Some(line) => match parse_ready_marker(&line) {
+                    Some(id) if id == command_id => return Ok(err_lines),
+                    Some(id) => {
+                        debug!("Discarding stderr output of earlier command {}", id);
+                        err_lines.clear();
+                    }
+                    None => err_lines.push(line),
+                },
+                None if err_lines.is_empty() => return Err(ExifToolError::StderrDisconnected),
+                None => {
+                    warn!("Stderr disconnected before the end of command output.");
+                    return Ok(err_lines);
+                }
+            }
+        }
+    }
+
+    /// Collects whatever is left on stderr after the process terminated.
+    async fn drain_remaining_stderr(&mut self) -> Vec<String> {
+        let mut err_lines = Vec::new();
+        let deadline = Instant::now() + STDERR_DRAIN_TIMEOUT;
+        while let Ok(Some(line)) = timeout_at(deadline, self.stderr_receiver.recv()).await {
+            if parse_ready_marker(&line).is_none() {
+                err_lines.push(line);
+            }
+        }
+        err_lines
+    }
+
+    /// Executes a command with the `-json` argument and parses the output into a [`Value`].
+    ///
+    /// Async equivalent of [`ExifTool::json_execute`](crate::ExifTool::json_execute).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn json_execute(&mut self, args: &[&str]) -> Result<Value, ExifToolError> {
+        let mut cmd_args = vec!["-json"];
+        cmd_args.extend_from_slice(args);
+        let output_bytes = self.execute_raw(&cmd_args).await?;
+        parse_json_output(args, &output_bytes)
+    }
+
+    /// Executes a typed [`Command`] and returns the raw byte output.
+    ///
+    /// Async equivalent of [`ExifTool::execute`](crate::ExifTool::execute).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn execute(&mut self, command: &Command) -> Result<Vec<u8>, ExifToolError> {
+        let args = command.to_args();
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.execute_raw(&arg_refs).await
+    }
+
+    /// Executes a typed [`Command`] with `-json` and returns one [`Value`] per processed file.
+    ///
+    /// Async equivalent of [`ExifTool::json_command`](crate::ExifTool::json_command).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn json_command(&mut self, command: &Command) -> Result<Vec<Value>, ExifToolError> {
+        let args = command.to_args();
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        match self.json_execute(&arg_refs).await? {
+            Value::Array(array) => Ok(array),
+            _ => Err(ExifToolError::UnexpectedFormat {
+                path: command
+                    .get_files()
+                    .iter()
+                    .map(|p| p.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                command_args: format!("-json {}", args.join(" ")),
+            }),
+        }
+    }
+
+    /// Executes a typed [`Command`] with `-json` and deserializes each file's output into `T`.
+    ///
+    /// Async equivalent of [`ExifTool::read_command`](crate::ExifTool::read_command).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn read_command<T: DeserializeOwned>(
+        &mut self,
+        command: &Command,
+    ) -> Result<Vec<T>, ExifToolError> {
+        self.json_command(command)
+            .await?
+            .into_iter()
+            .map(|value| serde_path_to_error::deserialize(value).map_err(ExifToolError::from))
+            .collect()
+    }
+
+    /// Reads metadata for multiple files, returning results as raw [`Value`]s.
+    ///
+    /// Async equivalent of [`ExifTool::json_batch`](crate::ExifTool::json_batch).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn json_batch<I, P>(
+        &mut self,
+        file_paths: I,
+        extra_args: &[&str],
+    ) -> Result<Vec<Value>, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let path_strs: Vec<String> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect();
+
+        if path_strs.is_empty() {
+            return Err(ExifToolError::UnexpectedFormat {
+                path: "".to_string(),
+                command_args: extra_args.join(","),
+            });
+        }
+
+        let mut args = extra_args.to_vec();
+        args.extend(path_strs.iter().map(String::as_str));
+
+        match self.json_execute(&args).await? {
+            Value::Array(array) => Ok(array),
+            _ => Err(ExifToolError::UnexpectedFormat {
+                path: path_strs.join(", "),
+                command_args: format!("-json {}", args.join(" ")),
+            }),
+        }
+    }
+
+    /// Reads metadata for multiple files, returning a separate result for every file.
+    ///
+    /// Async equivalent of
+    /// [`ExifTool::json_batch_results`](crate::ExifTool::json_batch_results).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn json_batch_results<I, P>(
+        &mut self,
+        file_paths: I,
+        extra_args: &[&str],
+    ) -> Result<Vec<BatchResult>, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let paths: Vec<PathBuf> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let path_strs: Vec<String> = paths
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        let mut args = vec!["-json"];
+        args.extend_from_slice(extra_args);
+        args.extend(path_strs.iter().map(String::as_str));
+
+        let output = self.execute_with_stderr(&args).await?;
+        split_batch_output(paths, &args, output)
+    }
+
+    /// Reads metadata for multiple files and also returns the warnings reported for them.
+    ///
+    /// Async equivalent of
+    /// [`ExifTool::json_batch_with_diagnostics`](crate::ExifTool::json_batch_with_diagnostics).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn json_batch_with_diagnostics<I, P>(
+        &mut self,
+        file_paths: I,
+        extra_args: &[&str],
+    ) -> Result<WithWarnings<Vec<Value>>, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let path_strs: Vec<String> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect();
+
+        if path_strs.is_empty() {
+            return Err(ExifToolError::UnexpectedFormat {
+                path: "".to_string(),
+                command_args: extra_args.join(","),
+            });
+        }
+
+        let mut args = vec!["-json"];
+        args.extend_from_slice(extra_args);
+        args.extend(path_strs.iter().map(String::as_str));
+
+        let output = self.execute_with_diagnostics(&args).await?;
+        json_batch_warnings(&args, output, &path_strs)
+    }
+
+    /// Reads metadata for a single file, returning a raw [`Value`].
+    ///
+    /// Async equivalent of [`ExifTool::json`](crate::ExifTool::json).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn json(
+        &mut self,
+        file_path: &Path,
+        extra_args: &[&str],
+    ) -> Result<Value, ExifToolError> {
+        let results = self
+            .json_batch(std::iter::once(file_path), extra_args)
+            .await?;
+
+        results
+            .into_iter()
+            .next()
+            .ok_or_else(|| ExifToolError::UnexpectedFormat {
+                path: file_path.to_string_lossy().into_owned(),
+                command_args: format!("{} {}", extra_args.join(" "), file_path.display()),
+            })
+    }
+
+    /// Reads a single tag's value as a raw [`Value`].
+    ///
+    /// Async equivalent of [`ExifTool::json_tag`](crate::ExifTool::json_tag).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn json_tag(&mut self, file_path: &Path, tag: &str) -> Result<Value, ExifToolError> {
+        let tag_arg = format!("-{}", tag);
+        let metadata_json = self.json(file_path, &[&tag_arg]).await?;
+
+        metadata_json
+            .get(tag)
+            .cloned()
+            .ok_or_else(|| ExifToolError::TagNotFound {
+                path: file_path.to_path_buf(),
+                tag: tag.to_string(),
+            })
+    }
+
+    /// Reads a single tag and deserializes its value into a target type `T`.
+    ///
+    /// Async equivalent of [`ExifTool::read_tag`](crate::ExifTool::read_tag), including the
+    /// handling of missing tags when `T` is an `Option`.
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn read_tag<T: DeserializeOwned>(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+    ) -> Result<T, ExifToolError> {
+        let value = self.json_tag(file_path, tag).await;
+        deserialize_tag(file_path, tag, value)
+    }
+
+    /// Reads tags with both their raw (`-n`) and print-converted values in one call.
+    ///
+    /// Async equivalent of [`ExifTool::read_tag_values`](crate::ExifTool::read_tag_values).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn read_tag_values(
+        &mut self,
+        file_path: &Path,
+        tags: &[&str],
+    ) -> Result<BTreeMap<String, TagValue>, ExifToolError> {
+        let args = tag_value_args(tags);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        Ok(parse_long_json(self.json(file_path, &args).await?))
+    }
+
+    /// Reads a single tag's raw and print-converted value.
+    ///
+    /// Async equivalent of [`ExifTool::read_tag_value`](crate::ExifTool::read_tag_value).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn read_tag_value(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+    ) -> Result<TagValue, ExifToolError> {
+        self.read_tag_values(file_path, &[tag])
+            .await?
+            .remove(tag)
+            .ok_or_else(|| ExifToolError::TagNotFound {
+                path: file_path.to_path_buf(),
+                tag: tag.to_string(),
+            })
+    }
+
+    /// Reads a single tag in the given [`Representation`] and deserializes it into `T`.
+    ///
+    /// Async equivalent of [`ExifTool::read_tag_as`](crate::ExifTool::read_tag_as).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn read_tag_as<T: DeserializeOwned>(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+        representation: Representation,
+    ) -> Result<T, ExifToolError> {
+        let value = match representation {
+            Representation::Printed => self.json_tag(file_path, tag).await,
+            Representation::Raw => {
+                let tag_arg = format!("-{}", tag);
+                self.json(file_path, &["-n", &tag_arg])
+                    .await
+                    .and_then(|metadata| {
+                        metadata
+                            .get(tag)
+                            .cloned()
+                            .ok_or_else(|| ExifToolError::TagNotFound {
+                                path: file_path.to_path_buf(),
+                                tag: tag.to_string(),
+                            })
+                    })
+            }
+        };
+        deserialize_tag(file_path, tag, value)
+    }
+
+    /// Reads a binary tag (like `ThumbnailImage`, `PreviewImage`) as raw bytes (`Vec<u8>`).
+    ///
+    /// Async equivalent of [`ExifTool::read_tag_binary`](crate::ExifTool::read_tag_binary).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn read_tag_binary(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+    ) -> Result<Vec<u8>, ExifToolError> {
+        let tag_arg = format!("-{}", tag);
+        let path_str = file_path.to_string_lossy();
+        let bytes = self
+            .execute_raw(&[path_str.as_ref(), "-b", &tag_arg])
+            .await?;
+
+        if bytes.is_empty() {
+            return Err(ExifToolError::TagNotFound {
+                path: file_path.to_path_buf(),
+                tag: tag.to_string(),
+            });
+        }
+        Ok(bytes)
+    }
+
+    /// Writes a value (converted to a string) to a specific tag in a file's metadata.
+    ///
+    /// Async equivalent of [`ExifTool::write_tag`](crate::ExifTool::write_tag).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn write_tag<T: ToString>(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+        value: T,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError> {
+        let tag_arg = format!("-{}={}", tag, value.to_string());
+        let path_str = file_path.to_string_lossy();
+
+        let mut args = vec![tag_arg.as_str()];
+        args.extend(self.backup_policy.arg());
+        args.extend_from_slice(extra_args);
+        args.push(path_str.as_ref());
+
+        let output = self.execute_with_stderr(&args).await?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Writes raw binary data to a specific tag (e.g., `ThumbnailImage`).
+    ///
+    /// Async equivalent of [`ExifTool::write_tag_binary`](crate::ExifTool::write_tag_binary).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn write_tag_binary<D: AsRef<[u8]>>(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+        data: D,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError> {
+        // The temp file is deleted when `temp_file` is dropped at the end of this scope.
+        let temp_file = NamedTempFile::new()?;
+        tokio::fs::write(temp_file.path(), data.as_ref()).await?;
+
+        let tag_arg = format!("-{}<={}", tag, temp_file.path().to_string_lossy());
+        let file_path_str = file_path.to_string_lossy();
+
+        let mut args = vec![tag_arg.as_str()];
+        args.extend(self.backup_policy.arg());
+        args.extend_from_slice(extra_args);
+        args.push(file_path_str.as_ref());
+
+        let output = self.execute_with_stderr(&args).await?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Restores files from their `{filename}_original` backups.
+    ///
+    /// Async equivalent of [`ExifTool::restore_original`](crate::ExifTool::restore_original).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn restore_original<I, P>(&mut self, file_paths: I) -> Result<usize, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.originals_command("-restore_original", file_paths)
+            .await
+    }
+
+    /// Deletes the `{filename}_original` backups of files.
+    ///
+    /// Async equivalent of [`ExifTool::delete_original`](crate::ExifTool::delete_original).
+    ///
+    /// # Errors
+    /// Returns the same [`ExifToolError`] variants as the blocking version.
+    pub async fn delete_original<I, P>(&mut self, file_paths: I) -> Result<usize, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.originals_command("-delete_original!", file_paths)
+            .await
+    }
+
+    /// Runs `-restore_original` or `-delete_original!` on the given files.
+    async fn originals_command<I, P>(
+        &mut self,
+        option: &str,
+        file_paths: I,
+    ) -> Result<usize, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let path_strs: Vec<String> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect();
+        if path_strs.is_empty() {
+            return Ok(0);
+        }
+
+        let mut args = vec![option];
+        args.extend(path_strs.iter().map(String::as_str));
+        let output = self.execute_with_stderr(&args).await?;
+        originals_count(&args, &output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_helpers::test_image_path;
+    use assert_matches::assert_matches;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_async_new_invalid_path() {
+        let result = AsyncExifTool::with_executable(Path::new("nonexistent_exiftool_command"));
+        assert_matches!(result, Err(ExifToolError::ExifToolNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_async_read_tag() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let path = test_image_path();
+
+        let make: String = et.read_tag(&path, "Make").await?;
+        assert_eq!(make, "Huawei");
+
+        let missing: Option<String> = et.read_tag(&path, "NonExistentTag123").await?;
+        assert!(missing.is_none());
+
+        et.close().await
+    }
+
+    #[tokio::test]
+    async fn test_async_timeout_restarts_process() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let path = test_image_path();
+
+        let result = et
+            .execute_raw_with_timeout(
+                &["-if", "sleep 10; 1", path.to_str().unwrap()],
+                Some(Duration::from_millis(500)),
+            )
+            .await;
+        assert_matches!(result, Err(ExifToolError::Timeout { .. }));
+
+        let make: String = et.read_tag(&path, "Make").await?;
+        assert_eq!(make, "Huawei");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_cancelled_command_output_is_discarded() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let path = test_image_path();
+        let pid = et.child.id();
+
+        // Drop the future while exiftool is still working on the command.
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(300),
+            et.execute_raw(&["-if", "sleep 1; 1", path.to_str().unwrap()]),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        let metadata = et.json(&path, &[]).await?;
+        assert_eq!(metadata["Make"], "Huawei");
+        // The cancelled command's output was drained instead of restarting the process.
+        assert_eq!(et.child.id(), pid);
+        et.close().await
+    }
+
+    #[tokio::test]
+    async fn test_async_json_batch() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let path1 = test_image_path();
+        let path2 = PathBuf::from("data/valid/other_images/jpg/gps/DSCN0010.jpg");
+        let results = et.json_batch([&path1, &path2], &["-FileName"]).await?;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[1].get("FileName").and_then(Value::as_str),
+            Some("DSCN0010.jpg")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_json_batch_results() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let missing = PathBuf::from("data/non_existent_file.jpg");
+        let results = et
+            .json_batch_results([test_image_path(), missing.clone()], &["-FileName"])
+            .await?;
+        assert!(results[0].1.is_ok());
+        assert_matches!(&results[1].1, Err(ExifToolError::FileNotFound { path, .. }) if *path == missing);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_file_not_found() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let path = Path::new("data/non_existent_file.jpg");
+        let result = et.json(path, &[]).await;
+        assert_matches!(result, Err(ExifToolError::FileNotFound { path: p, .. }) if p == path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_execute_with_stderr() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let output = et
+            .execute_with_stderr(&["-json", "data/non_existent_file.jpg"])
+            .await?;
+        assert_eq!(
+            output.stderr,
+            vec!["Error: File not found - data/non_existent_file.jpg"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_write_tag() -> Result<(), ExifToolError> {
+        let mut et = AsyncExifTool::new()?;
+        let (_, temp_img) = tempfile::Builder::new()
+            .suffix(".jpg")
+            .tempfile_in("data")?
+            .keep()
+            .map_err(|e| e.error)?;
+        fs::copy(test_image_path(), &temp_img)?;
+
+        et.write_tag(
+            &temp_img,
+            "Author",
+            "Async Writer",
+            &["-overwrite_original"],
+        )
+        .await?;
+        let author: String = et.read_tag(&temp_img, "Author").await?;
+        fs::remove_file(&temp_img)?;
+
+        assert_eq!(author, "Async Writer");
+        Ok(())
+    }
+}
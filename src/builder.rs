@@ -0,0 +1,297 @@
+use crate::error::ExifToolError;
+use crate::exiftool::ExifTool;
+use crate::write::BackupPolicy;
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+/// Configures how the persistent `exiftool` process is spawned.
+///
+/// Created with [`ExifTool::builder`] or [`ExifToolBuilder::new`]. Every setting is optional;
+/// a default builder behaves exactly like [`ExifTool::new`].
+///
+/// The configuration is kept by the resulting [`ExifTool`], so a process that gets restarted
+/// (for example after a [timeout](ExifToolBuilder::timeout)) is spawned with the same options.
+///
+/// # Examples
+///
+/// ```no_run
+/// use exiftool::{ExifTool, ExifToolError};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), ExifToolError> {
+/// let mut et = ExifTool::builder()
+///     .executable("/opt/local/bin/exiftool")
+///     .config("my_tags.config")
+///     .charset("filename=utf8")
+///     .common_args(["-n"])
+///     .env("TZ", "UTC")
+///     .current_dir("/photos")
+///     .timeout(Duration::from_secs(30))
+///     .build()?;
+///
+/// // `-n` is applied to every command, so numeric values are returned.
+/// let width: u32 = et.read_tag("image.jpg".as_ref(), "ImageWidth")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExifToolBuilder {
+    executable: PathBuf,
+    config: Option<PathBuf>,
+    charsets: Vec<String>,
+    common_args: Vec<String>,
+    env: Vec<(OsString, OsString)>,
+    current_dir: Option<PathBuf>,
+    timeout: Option<Duration>,
+    backup_policy: BackupPolicy,
+}
+
+impl Default for ExifToolBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExifToolBuilder {
+    /// Creates a builder with default settings: `exiftool` from the system `PATH`, no config
+    /// file, no common arguments, no timeout and [`BackupPolicy::KeepOriginal`].
+    pub fn new() -> Self {
+        Self {
+            executable: PathBuf::from("exiftool"),
+            config: None,
+            charsets: Vec::new(),
+            common_args: Vec::new(),
+            env: Vec::new(),
+            current_dir: None,
+            timeout: None,
+            backup_policy: BackupPolicy::KeepOriginal,
+        }
+    }
+
+    /// Sets the path to the `exiftool` executable.
+    pub fn executable(mut self, path: impl Into<PathBuf>) -> Self {
+        self.executable = path.into();
+        self
+    }
+
+    /// Loads a custom configuration file (`-config`), e.g. to define user tags.
+    pub fn config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config = Some(path.into());
+        self
+    }
+
+    /// Adds a `-charset` option applied to every command, e.g. `"filename=utf8"` or
+    /// `"exif=latin"`. Can be called multiple times.
+    pub fn charset(mut self, charset: impl Into<String>) -> Self {
+        self.charsets.push(charset.into());
+        self
+    }
+
+    /// Adds arguments that are applied to every command (`-common_args`).
+    /// Can be called multiple times; the arguments accumulate.
+    pub fn common_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.common_args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets an environment variable for the `exiftool` process.
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.env
+            .push((key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+        self
+    }
+
+    /// Sets the working directory of the `exiftool` process.
+    /// Relative file paths in commands are resolved against it.
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the initial per-command timeout, see [`ExifTool::set_timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the initial backup policy for writes, see [`ExifTool::set_backup_policy`].
+    pub fn backup_policy(mut self, policy: BackupPolicy) -> Self {
+        self.backup_policy = policy;
+        self
+    }
+
+    /// Launches the `exiftool` process with this configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExifToolError::ExifToolNotFound`] if the executable cannot be found or the
+    /// process fails to start.
+    /// Returns [`ExifToolError::Io`] if capturing the stdin/stdout/stderr pipes fails.
+    pub fn build(&self) -> Result<ExifTool, ExifToolError> {
+        ExifTool::spawn(self.clone())
+    }
+
+    /// Launches an [`AsyncExifTool`](crate::AsyncExifTool) with this configuration.
+    ///
+    /// Must be called from within a Tokio runtime. Requires the `tokio` feature.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifToolBuilder::build`].
+    #[cfg(feature = "tokio")]
+    pub fn build_async(&self) -> Result<crate::AsyncExifTool, ExifToolError> {
+        crate::AsyncExifTool::spawn(self.clone())
+    }
+
+    /// The initial per-command timeout.
+    pub(crate) fn initial_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The initial backup policy for writes.
+    pub(crate) fn initial_backup_policy(&self) -> BackupPolicy {
+        self.backup_policy
+    }
+
+    /// Builds the stay-open command line. Pipes are left to the caller.
+    pub(crate) fn command(&self) -> Command {
+        let mut command = self.base_command();
+        command.arg("-stay_open").arg("True").arg("-@").arg("-"); // Read command args from stdin
+
+        // `-common_args` must come last, everything after it is appended to each command.
+        if !self.charsets.is_empty() || !self.common_args.is_empty() {
+            command.arg("-common_args");
+            for charset in &self.charsets {
+                command.arg("-charset").arg(charset);
+            }
+            command.args(&self.common_args);
+        }
+        command
+    }
+
+    /// Builds a command line that runs a single command with `args` and exits, with the same
+    /// configuration as the stay-open process. Pipes are left to the caller.
+    pub(crate) fn oneshot_command(&self, args: &[&str]) -> Command {
+        let mut command = self.base_command();
+        command.args(args);
+        for charset in &self.charsets {
+            command.arg("-charset").arg(charset);
+        }
+        command.args(&self.common_args);
+        command
+    }
+
+    /// The executable with `-config`, environment and working directory applied.
+    fn base_command(&self) -> Command {
+        let mut command = Command::new(&self.executable);
+        // `-config` is only honoured as the very first argument.
+        if let Some(config) = &self.config {
+            command.arg("-config").arg(config);
+        }
+        command.envs(self.env.iter().map(|(k, v)| (k, v)));
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+        command
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_helpers::test_image_path;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn test_command_line() {
+        let builder = ExifToolBuilder::new()
+            .executable("/usr/bin/exiftool")
+            .config("tags.config")
+            .charset("filename=utf8")
+            .common_args(["-n", "-fast"]);
+        let command = builder.command();
+        let args: Vec<_> = command.get_args().collect();
+
+        assert_eq!(command.get_program(), "/usr/bin/exiftool");
+        assert_eq!(
+            args,
+            [
+                "-config",
+                "tags.config",
+                "-stay_open",
+                "True",
+                "-@",
+                "-",
+                "-common_args",
+                "-charset",
+                "filename=utf8",
+                "-n",
+                "-fast"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oneshot_command_line() {
+        let builder = ExifToolBuilder::new()
+            .config("tags.config")
+            .charset("filename=utf8")
+            .common_args(["-n"]);
+        let command = builder.oneshot_command(&["-json", "-"]);
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(
+            args,
+            [
+                "-config",
+                "tags.config",
+                "-json",
+                "-",
+                "-charset",
+                "filename=utf8",
+                "-n"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_command_line() {
+        let command = ExifToolBuilder::new().command();
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(command.get_program(), "exiftool");
+        assert_eq!(args, ["-stay_open", "True", "-@", "-"]);
+    }
+
+    #[test]
+    fn test_build_invalid_path() {
+        let result = ExifTool::builder()
+            .executable("nonexistent_exiftool_command")
+            .build();
+        assert_matches!(result, Err(ExifToolError::ExifToolNotFound(_)));
+    }
+
+    #[test]
+    fn test_build_with_options() -> Result<(), ExifToolError> {
+        let data_dir = std::env::current_dir()?.join("data");
+        let mut et = ExifTool::builder()
+            .current_dir(&data_dir)
+            .charset("filename=utf8")
+            .timeout(Duration::from_secs(30))
+            .backup_policy(BackupPolicy::OverwriteOriginal)
+            .build()?;
+        assert_eq!(et.timeout(), Some(Duration::from_secs(30)));
+        assert_eq!(et.backup_policy(), BackupPolicy::OverwriteOriginal);
+
+        // Relative paths resolve against the configured working directory.
+        let relative = test_image_path();
+        let relative = relative.strip_prefix("data").unwrap();
+        let make: String = et.read_tag(relative, "Make")?;
+        assert_eq!(make, "Huawei");
+        Ok(())
+    }
+}
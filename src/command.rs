@@ -0,0 +1,311 @@
+use std::path::{Path, PathBuf};
+
+/// The group family used to organize output, see `exiftool -g[NUM]`.
+///
+/// Each family groups the same tags along a different axis, e.g. `EXIF`/`XMP` (where the tag
+/// is stored) or `Camera`/`Location` (what the tag describes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupLevel {
+    /// Family 0 (`-g0`): general location, e.g. `EXIF`, `XMP`, `File`, `Composite`.
+    General,
+    /// Family 1 (`-g1`): specific location, e.g. `IFD0`, `ExifIFD`, `XMP-dc`.
+    Specific,
+    /// Family 2 (`-g2`): category, e.g. `Camera`, `Image`, `Location`, `Time`.
+    /// Matches the layout of [`ExifData`](crate::g2::ExifData).
+    Category,
+    /// Family 3 (`-g3`): document number, e.g. `Main`, `Doc1`.
+    Document,
+    /// Family 4 (`-g4`): instance number, e.g. `Copy1`.
+    Instance,
+    /// Family 5 (`-g5`): metadata path, e.g. `JPEG-APP1-IFD0`.
+    Path,
+    /// Family 6 (`-g6`): EXIF/TIFF format, e.g. `int16u`, `string`.
+    Format,
+    /// Family 7 (`-g7`): tag ID, e.g. `ID-0x010f`.
+    TagId,
+}
+
+impl GroupLevel {
+    /// The numeric family of this group level.
+    pub fn family(self) -> u8 {
+        match self {
+            GroupLevel::General => 0,
+            GroupLevel::Specific => 1,
+            GroupLevel::Category => 2,
+            GroupLevel::Document => 3,
+            GroupLevel::Instance => 4,
+            GroupLevel::Path => 5,
+            GroupLevel::Format => 6,
+            GroupLevel::TagId => 7,
+        }
+    }
+}
+
+/// Sort direction for [`Command::file_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// A typed `exiftool` command, rendered to an argument list with [`Command::to_args`].
+///
+/// Use this instead of string slices like `&["-g2", "-n"]` so options are checked at compile
+/// time. Run it with [`ExifTool::execute`](crate::ExifTool::execute),
+/// [`ExifTool::json_command`](crate::ExifTool::json_command) or
+/// [`ExifTool::read_command`](crate::ExifTool::read_command).
+///
+/// Options that have no dedicated method can still be passed with [`Command::arg`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use exiftool::{Command, ExifTool, ExifToolError, GroupLevel, SortOrder};
+///
+/// # fn main() -> Result<(), ExifToolError> {
+/// let mut et = ExifTool::new()?;
+/// let command = Command::new()
+///     .file("photos")
+///     .recursive()
+///     .extension("jpg")
+///     .group(GroupLevel::Category)
+///     .numeric()
+///     .tags(["Make", "Model", "DateTimeOriginal"])
+///     .condition("$Make eq 'Canon'")
+///     .file_order("DateTimeOriginal", SortOrder::Ascending);
+///
+/// for metadata in et.json_command(&command)? {
+///     println!("{}", metadata);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Command {
+    files: Vec<PathBuf>,
+    group: Option<GroupLevel>,
+    numeric: bool,
+    tags: Vec<String>,
+    excluded_tags: Vec<String>,
+    conditions: Vec<String>,
+    file_order: Vec<(String, SortOrder)>,
+    recursive: bool,
+    extensions: Vec<String>,
+    excluded_extensions: Vec<String>,
+    extra_args: Vec<String>,
+}
+
+impl Command {
+    /// Creates an empty command.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file or directory to process.
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        self.files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds multiple files or directories to process.
+    pub fn files<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.files
+            .extend(paths.into_iter().map(|p| p.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Organizes output by tag group (`-g[NUM]`).
+    pub fn group(mut self, level: GroupLevel) -> Self {
+        self.group = Some(level);
+        self
+    }
+
+    /// Returns values in machine-readable numeric form instead of print conversion (`-n`),
+    /// e.g. `1` instead of `"Horizontal (normal)"`.
+    pub fn numeric(mut self) -> Self {
+        self.numeric = true;
+        self
+    }
+
+    /// Extracts only this tag (`-TAG`). Can be called multiple times.
+    /// Group-qualified names like `EXIF:Make` and wildcards like `*Date` are allowed.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Extracts only these tags, see [`Command::tag`].
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Excludes a tag or group from the output (`--TAG`), e.g. `"ThumbnailImage"` or `"XMP:all"`.
+    pub fn exclude(mut self, tag: impl Into<String>) -> Self {
+        self.excluded_tags.push(tag.into());
+        self
+    }
+
+    /// Only processes files for which the Perl expression is true (`-if EXPR`), e.g.
+    /// `"$ImageWidth > 1000"`. Multiple conditions must all be true.
+    pub fn condition(mut self, expr: impl Into<String>) -> Self {
+        self.conditions.push(expr.into());
+        self
+    }
+
+    /// Processes files ordered by the value of a tag (`-fileOrder [-]TAG`).
+    /// Calling this again adds a secondary sort key.
+    pub fn file_order(mut self, tag: impl Into<String>, order: SortOrder) -> Self {
+        self.file_order.push((tag.into(), order));
+        self
+    }
+
+    /// Recursively processes files in subdirectories (`-r`).
+    pub fn recursive(mut self) -> Self {
+        self.recursive = true;
+        self
+    }
+
+    /// Only processes files with this extension when scanning directories (`-ext EXT`).
+    /// The extension is given without the leading dot.
+    pub fn extension(mut self, ext: impl Into<String>) -> Self {
+        self.extensions.push(ext.into());
+        self
+    }
+
+    /// Skips files with this extension when scanning directories (`--ext EXT`).
+    pub fn exclude_extension(mut self, ext: impl Into<String>) -> Self {
+        self.excluded_extensions.push(ext.into());
+        self
+    }
+
+    /// Adds a raw argument for options without a dedicated method, e.g. `"-fast"`.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.extra_args.push(arg.into());
+        self
+    }
+
+    /// The files and directories this command processes.
+    pub fn get_files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Renders the command to an `exiftool` argument list: options first, then tags,
+    /// then files.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(level) = self.group {
+            args.push(format!("-g{}", level.family()));
+        }
+        if self.numeric {
+            args.push("-n".to_string());
+        }
+        if self.recursive {
+            args.push("-r".to_string());
+        }
+        for ext in &self.extensions {
+            args.extend(["-ext".to_string(), ext.clone()]);
+        }
+        for ext in &self.excluded_extensions {
+            args.extend(["--ext".to_string(), ext.clone()]);
+        }
+        for condition in &self.conditions {
+            args.extend(["-if".to_string(), condition.clone()]);
+        }
+        for (tag, order) in &self.file_order {
+            let prefix = match order {
+                SortOrder::Ascending => "",
+                SortOrder::Descending => "-",
+            };
+            args.extend(["-fileOrder".to_string(), format!("{}{}", prefix, tag)]);
+        }
+        args.extend(self.extra_args.iter().cloned());
+        args.extend(self.tags.iter().map(|tag| format!("-{}", tag)));
+        args.extend(self.excluded_tags.iter().map(|tag| format!("--{}", tag)));
+        args.extend(
+            self.files
+                .iter()
+                .map(|path| path.to_string_lossy().into_owned()),
+        );
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ExifToolError;
+    use crate::utils::test_helpers::test_image_path;
+    use crate::ExifTool;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn test_to_args() {
+        let command = Command::new()
+            .file("photos")
+            .recursive()
+            .extension("jpg")
+            .exclude_extension("png")
+            .group(GroupLevel::Category)
+            .numeric()
+            .tags(["Make", "Model"])
+            .exclude("ThumbnailImage")
+            .condition("$Make eq 'Canon'")
+            .file_order("DateTimeOriginal", SortOrder::Descending)
+            .arg("-fast");
+
+        assert_eq!(
+            command.to_args(),
+            [
+                "-g2",
+                "-n",
+                "-r",
+                "-ext",
+                "jpg",
+                "--ext",
+                "png",
+                "-if",
+                "$Make eq 'Canon'",
+                "-fileOrder",
+                "-DateTimeOriginal",
+                "-fast",
+                "-Make",
+                "-Model",
+                "--ThumbnailImage",
+                "photos",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_command() {
+        assert!(Command::new().to_args().is_empty());
+    }
+
+    #[test]
+    fn test_json_command() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let command = Command::new().file(test_image_path()).tag("Make");
+        let results = et.json_command(&command)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["Make"], "Huawei");
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_command_file_not_found() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let command = Command::new().file("data/non_existent_file.jpg");
+        let result = et.json_command(&command);
+        assert_matches!(result, Err(ExifToolError::FileNotFound { .. }));
+        Ok(())
+    }
+}
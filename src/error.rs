@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur when interacting with ExifTool.
@@ -28,9 +29,23 @@ pub enum ExifToolError {
         command_args: String,
     },
 
+    #[error(
+        "ExifTool did not update any files. Command: exiftool {command_args}, std_err: {std_err}"
+    )]
+    NoFilesUpdated {
+        command_args: String,
+        std_err: String,
+    },
+
     #[error("ExifTool process terminated unexpectedly.")]
     ProcessTerminated,
 
+    #[error("ExifTool command timed out after {elapsed:?}. Command: exiftool {command_args}")]
+    Timeout {
+        command_args: String,
+        elapsed: Duration,
+    },
+
     #[error("ExifTool stderr stream disconnected.")]
     StderrDisconnected,
 
@@ -64,3 +79,72 @@ impl From<serde_path_to_error::Error<serde_json::Error>> for ExifToolError {
         }
     }
 }
+
+impl ExifToolError {
+    /// A copy of this error, to report one failure (e.g. a timed out batch) for several
+    /// files. The error isn't `Clone` because of its sources; `io` and `serde_json` sources
+    /// are re-created from their kind and message.
+    pub(crate) fn duplicate(&self) -> Self {
+        fn io_copy(e: &std::io::Error) -> std::io::Error {
+            std::io::Error::new(e.kind(), e.to_string())
+        }
+        fn json_copy(e: &serde_json::Error) -> serde_json::Error {
+            serde::de::Error::custom(e.to_string())
+        }
+        match self {
+            Self::ExifToolNotFound(e) => Self::ExifToolNotFound(io_copy(e)),
+            Self::Io(e) => Self::Io(io_copy(e)),
+            Self::Json(e) => Self::Json(json_copy(e)),
+            Self::Utf8(e) => match String::from_utf8(e.as_bytes().to_vec()) {
+                Err(e) => Self::Utf8(e),
+                Ok(_) => unreachable!("bytes of a FromUtf8Error are invalid UTF-8"),
+            },
+            Self::FileNotFound { path, command_args } => Self::FileNotFound {
+                path: path.clone(),
+                command_args: command_args.clone(),
+            },
+            Self::ExifToolProcess {
+                message,
+                std_err,
+                command_args,
+            } => Self::ExifToolProcess {
+                message: message.clone(),
+                std_err: std_err.clone(),
+                command_args: command_args.clone(),
+            },
+            Self::NoFilesUpdated {
+                command_args,
+                std_err,
+            } => Self::NoFilesUpdated {
+                command_args: command_args.clone(),
+                std_err: std_err.clone(),
+            },
+            Self::ProcessTerminated => Self::ProcessTerminated,
+            Self::Timeout {
+                command_args,
+                elapsed,
+            } => Self::Timeout {
+                command_args: command_args.clone(),
+                elapsed: *elapsed,
+            },
+            Self::StderrDisconnected => Self::StderrDisconnected,
+            Self::UnexpectedFormat { path, command_args } => Self::UnexpectedFormat {
+                path: path.clone(),
+                command_args: command_args.clone(),
+            },
+            Self::TagNotFound { path, tag } => Self::TagNotFound {
+                path: path.clone(),
+                tag: tag.clone(),
+            },
+            Self::Deserialization { path, source } => Self::Deserialization {
+                path: path.clone(),
+                source: json_copy(source),
+            },
+            Self::TagDeserialization { path, tag, error } => Self::TagDeserialization {
+                path: path.clone(),
+                tag: tag.clone(),
+                error: json_copy(error),
+            },
+        }
+    }
+}
@@ -1,20 +1,47 @@
+use crate::builder::ExifToolBuilder;
+use crate::command::Command;
 use crate::error::ExifToolError;
-use log::warn;
+use crate::metadata_iter::MetadataIter;
+use crate::oneshot::run_oneshot;
+use crate::sidecar::{merge_sidecar, SidecarNaming, SidecarPrecedence};
+use crate::tag_value::{
+    deserialize_tag, parse_long_json, tag_value_args, Representation, TagValue,
+};
+use crate::warning::{stderr_warnings, Warning, WithWarnings};
+use crate::write::{
+    originals_count, BackupPolicy, CopySelection, FileChanges, StripPolicy, WriteReport, WriteSet,
+};
+use log::{debug, warn};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
-use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
 use std::thread;
 use std::time::{Duration, Instant};
 use tempfile::NamedTempFile;
 
 use serde::de::DeserializeOwned;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
-// std err can come in a tiny bit delayed after stdout, in which case we have to wait a
-// millisecond or 2 to be able to read errors and warnings.
-const STDERR_POLL_INTERVAL: Duration = Duration::from_millis(1);
-const STDERR_POLL_TIMEOUT: Duration = Duration::from_millis(2);
+// How long to keep collecting stderr after the process terminated unexpectedly.
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The complete output of a single `exiftool` command.
+///
+/// Returned by [`ExifTool::execute_with_stderr`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// Everything the command wrote to stdout.
+    pub stdout: Vec<u8>,
+    /// Every line the command wrote to stderr (errors and warnings), in order.
+    pub stderr: Vec<String>,
+}
+
+/// The outcome of processing one file in a batch: the input path and its metadata or error.
+///
+/// Returned by [`ExifTool::json_batch_results`].
+pub type BatchResult = (PathBuf, Result<Value, ExifToolError>);
 
 /// Interacts with a persistent `exiftool` command-line process.
 ///
@@ -23,7 +50,11 @@ const STDERR_POLL_TIMEOUT: Duration = Duration::from_millis(2);
 /// a new process each time.
 ///
 /// Communication happens via the process's standard input, output, and error streams.
-/// An internal thread monitors stderr for errors and warnings.
+/// Internal threads read stdout and monitor stderr for errors and warnings.
+///
+/// An optional timeout can be configured with [`ExifTool::set_timeout`]. When a command
+/// exceeds it, the hung process is killed and replaced by a fresh one, so the instance
+/// remains usable.
 ///
 /// Most methods require `&mut self` because each command involves stateful interaction
 /// with the underlying process (sending commands via stdin, reading responses from stdout/stderr).
@@ -55,9 +86,15 @@ const STDERR_POLL_TIMEOUT: Duration = Duration::from_millis(2);
 #[derive(Debug)]
 pub struct ExifTool {
     stdin: BufWriter<ChildStdin>,
-    stdout: BufReader<ChildStdout>,
+    stdout_receiver: Receiver<Vec<u8>>,
     stderr_receiver: Receiver<String>,
     child: Child,
+    terminated: bool,
+    builder: ExifToolBuilder,
+    timeout: Option<Duration>,
+    backup_policy: BackupPolicy,
+    sidecar_naming: SidecarNaming,
+    command_id: u64,
 }
 
 impl ExifTool {
@@ -116,11 +153,34 @@ impl ExifTool {
     /// # }
     /// ```
     pub fn with_executable(exiftool_path: &Path) -> Result<Self, ExifToolError> {
-        let mut child = Command::new(exiftool_path)
-            .arg("-stay_open")
-            .arg("True")
-            .arg("-@")
-            .arg("-") // Read command args from stdin
+        ExifToolBuilder::new().executable(exiftool_path).build()
+    }
+
+    /// Returns an [`ExifToolBuilder`] to configure the `exiftool` process before launching it,
+    /// e.g. with a config file, common arguments or environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let et = ExifTool::builder()
+    ///     .charset("filename=utf8")
+    ///     .common_args(["-api", "LargeFileSupport=1"])
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> ExifToolBuilder {
+        ExifToolBuilder::new()
+    }
+
+    /// Launches the stay-open process described by `builder`.
+    /// Internal helper function, used by [`ExifToolBuilder::build`].
+    pub(crate) fn spawn(builder: ExifToolBuilder) -> Result<Self, ExifToolError> {
+        let mut child = builder
+            .command()
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -140,6 +200,20 @@ impl ExifTool {
             .take()
             .ok_or_else(|| std::io::Error::other("Failed to capture stderr"))?;
 
+        // Spawn a thread to continuously read stdout, so reads can be bounded by a timeout.
+        let (stdout_sender, stdout_receiver) = mpsc::channel();
+        let mut stdout_reader = stdout;
+        thread::spawn(move || {
+            let mut chunk = [0u8; 4096];
+            // Send output chunk by chunk. On EOF or a read error the sender is dropped,
+            // which the receiving side treats as the process having terminated.
+            while let Ok(bytes_read) = stdout_reader.read(&mut chunk) {
+                if bytes_read == 0 || stdout_sender.send(chunk[..bytes_read].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
         // Spawn a thread to continuously read stderr
         let (stderr_sender, stderr_receiver) = mpsc::channel();
         let stderr_reader = BufReader::new(stderr);
@@ -156,19 +230,103 @@ impl ExifTool {
 
         Ok(Self {
             stdin: BufWriter::new(stdin),
-            stdout: BufReader::new(stdout),
+            stdout_receiver,
             stderr_receiver,
             child,
+            terminated: false,
+            timeout: builder.initial_timeout(),
+            backup_policy: builder.initial_backup_policy(),
+            sidecar_naming: SidecarNaming::default(),
+            builder,
+            command_id: 0,
         })
     }
 
+    /// Returns the timeout applied to every command, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets a timeout applied to every command executed by this instance.
+    ///
+    /// When a command does not complete in time, [`ExifToolError::Timeout`] is returned,
+    /// the hung `exiftool` process is killed and a fresh stay-open process is started in
+    /// its place. Pass `None` (the default) to wait indefinitely.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    /// use std::path::Path;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// et.set_timeout(Some(Duration::from_secs(10)));
+    ///
+    /// match et.json(Path::new("suspicious.jpg"), &[]) {
+    ///     Err(ExifToolError::Timeout { elapsed, .. }) => println!("Gave up after {:?}", elapsed),
+    ///     other => println!("{:?}", other?),
+    /// }
+    /// // `et` is still usable here, even if the previous command timed out.
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the backup policy applied to writes.
+    pub fn backup_policy(&self) -> BackupPolicy {
+        self.backup_policy
+    }
+
+    /// Sets what happens to the original file when [`ExifTool::write_tag`],
+    /// [`ExifTool::write_tag_binary`], [`ExifTool::write_tags`] or [`ExifTool::copy_tags`]
+    /// modify it.
+    ///
+    /// The default, [`BackupPolicy::KeepOriginal`], leaves a `{filename}_original` backup next
+    /// to every written file, like `exiftool` itself.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exiftool::{BackupPolicy, ExifTool, ExifToolError};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// et.set_backup_policy(BackupPolicy::OverwriteOriginal);
+    ///
+    /// // No `image.jpg_original` is created.
+    /// et.write_tag(Path::new("image.jpg"), "Artist", "Jane Doe", &[])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_backup_policy(&mut self, policy: BackupPolicy) {
+        self.backup_policy = policy;
+    }
+
+    /// Returns how XMP sidecars are named.
+    pub fn sidecar_naming(&self) -> SidecarNaming {
+        self.sidecar_naming
+    }
+
+    /// Sets how [`ExifTool::create_sidecar`], [`ExifTool::sync_to_sidecar`],
+    /// [`ExifTool::sync_from_sidecar`] and [`ExifTool::json_with_sidecar`] find a file's
+    /// sidecar. Defaults to [`SidecarNaming::ReplaceExtension`] (`IMG_0001.xmp`).
+    pub fn set_sidecar_naming(&mut self, naming: SidecarNaming) {
+        self.sidecar_naming = naming;
+    }
+
     // --- Core Execution Logic ---
 
     /// Executes a command with the provided arguments and returns the raw byte output.
     ///
     /// This is the low-level method used by other helpers. It sends arguments line by line
-    /// to the `exiftool` process's stdin, followed by `-execute\\n`, reads the response
-    /// from stdout until `{ready}\\n`, and checks stderr for errors.
+    /// to the `exiftool` process's stdin, followed by a numbered `-execute<N>`, reads the
+    /// response from stdout until `{ready<N>}`, and checks this command's stderr output
+    /// (delimited with an `-echo4` marker) for errors.
     ///
     /// **Note:** This method is typically not needed for common use cases. Prefer using
     /// methods like [`ExifTool::execute_lines`], [`ExifTool::json`], [`ExifTool::read_tag`],
@@ -185,133 +343,298 @@ impl ExifTool {
     /// * [`ExifToolError::ExifToolProcess`]: If `exiftool` reports other errors on stderr.
     /// * [`ExifToolError::ProcessTerminated`]: If the process exits unexpectedly.
     /// * [`ExifToolError::StderrDisconnected`]: If the stderr monitoring fails.
+    /// * [`ExifToolError::Timeout`]: If the command exceeds the timeout set with
+    ///   [`ExifTool::set_timeout`].
     pub fn execute_raw(&mut self, args: &[&str]) -> Result<Vec<u8>, ExifToolError> {
-        // 1. Clear any stale errors from previous commands
-        while self.stderr_receiver.try_recv().is_ok() {}
+        self.execute_raw_with_timeout(args, self.timeout)
+    }
+
+    /// Like [`ExifTool::execute_raw`], but with a timeout for this call only.
+    ///
+    /// The given `timeout` overrides the one set with [`ExifTool::set_timeout`]; `None`
+    /// waits indefinitely.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`ExifTool::execute_raw`]. On [`ExifToolError::Timeout`],
+    /// the hung process has been replaced by a fresh one before returning.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let result = et.execute_raw_with_timeout(
+    ///     &["-S", "-Make", "huge_video.mp4"],
+    ///     Some(Duration::from_secs(30)),
+    /// );
+    /// if let Err(ExifToolError::Timeout { .. }) = result {
+    ///     println!("exiftool took too long, process was restarted.");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_raw_with_timeout(
+        &mut self,
+        args: &[&str],
+        timeout: Option<Duration>,
+    ) -> Result<Vec<u8>, ExifToolError> {
+        let output = self.execute_command(args, timeout)?;
+
+        // Process results and errors
+        check_stderr(args, &output.stderr)?;
+
+        // If stderr contained only warnings or was empty, return the stdout bytes
+        Ok(output.stdout)
+    }
+
+    /// Executes a command and returns its stdout together with *all* of its stderr lines.
+    ///
+    /// Unlike [`ExifTool::execute_raw`], errors reported by `exiftool` on stderr are not turned
+    /// into an [`ExifToolError`]; they are returned as-is in [`CommandOutput::stderr`]. Every
+    /// command's stderr output is delimited by a marker that `exiftool` prints when the command
+    /// finishes, so the lines returned here always belong to this command and this command only.
+    ///
+    /// # Arguments
+    /// * `args` - A slice of string arguments to pass to `exiftool`.
+    ///
+    /// # Errors
+    /// Returns [`ExifToolError::Io`], [`ExifToolError::ProcessTerminated`],
+    /// [`ExifToolError::StderrDisconnected`] or [`ExifToolError::Timeout`] if communicating
+    /// with the process fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let output = et.execute_with_stderr(&["-S", "-Make", "image.jpg", "missing.jpg"])?;
+    /// println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    /// for line in &output.stderr {
+    ///     println!("stderr: {}", line); // "Error: File not found - missing.jpg"
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_with_stderr(&mut self, args: &[&str]) -> Result<CommandOutput, ExifToolError> {
+        self.execute_command(args, self.timeout)
+    }
+
+    /// Executes a command like [`ExifTool::execute_raw`], and also returns the warnings
+    /// `exiftool` printed while running it.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`ExifTool::execute_raw`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let result = et.execute_with_diagnostics(&["-Comment=Hi", "image.jpg"])?;
+    /// for warning in &result.warnings {
+    ///     println!("{} (minor: {})", warning.message, warning.minor);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_with_diagnostics(
+        &mut self,
+        args: &[&str],
+    ) -> Result<WithWarnings<Vec<u8>>, ExifToolError> {
+        let output = self.execute_command(args, self.timeout)?;
+        check_stderr(args, &output.stderr)?;
+        Ok(WithWarnings {
+            value: output.stdout,
+            warnings: stderr_warnings(args, &output.stderr),
+        })
+    }
+
+    /// Sends one command to the process and collects its complete stdout and stderr output.
+    /// Internal helper function.
+    fn execute_command(
+        &mut self,
+        args: &[&str],
+        timeout: Option<Duration>,
+    ) -> Result<CommandOutput, ExifToolError> {
+        let start_time = Instant::now();
+        self.command_id += 1;
+        let command_id = self.command_id;
 
-        // 2. Send command arguments line-by-line
+        // 1. Send command arguments line-by-line
         for arg in args {
             writeln!(self.stdin, "{}", arg)?;
         }
-        // 3. Send the execute signal
-        writeln!(self.stdin, "-execute")?;
+        // 2. Have exiftool mark the end of this command's stderr output, then send the
+        //    numbered execute signal, which ends stdout output with `{ready<N>}`.
+        writeln!(self.stdin, "-echo4")?;
+        writeln!(self.stdin, "{}", ready_marker(command_id))?;
+        writeln!(self.stdin, "-execute{}", command_id)?;
         self.stdin.flush()?;
 
-        // 4. Read the response from stdout
-        let stdout_bytes = self.read_response_until_ready()?;
-
-        // 5. Check for errors on stderr
-        let stderr_lines = self.drain_stderr()?;
-
-        // 6. Process results and errors
-        if !stderr_lines.is_empty() {
-            // Combine args for error reporting
-            let command_args = args.join(" ");
-            let combined_stderr = stderr_lines.join("\n");
-
-            // Check for specific common errors first
-            for err_line in &stderr_lines {
-                if let Some(filename) = err_line.strip_prefix("Error: File not found - ") {
-                    return Err(ExifToolError::FileNotFound {
-                        path: PathBuf::from(filename.trim()),
-                        command_args,
-                    });
-                } else if err_line.contains("Error:") {
-                    return Err(ExifToolError::ExifToolProcess {
-                        message: err_line.to_string(),
-                        std_err: combined_stderr,
-                        command_args,
-                    });
-                } else if err_line.contains("Warning:") {
-                    warn!("ExifTool Warning - {}", err_line);
+        // 3. Read the response from stdout and this command's lines from stderr
+        let deadline = timeout.map(|t| start_time + t);
+        let response = self
+            .read_response_until_ready(command_id, deadline)
+            .and_then(|stdout| Ok((stdout, self.read_stderr_until_ready(command_id, deadline)?)));
+        match response {
+            Err(ExifToolError::Timeout { .. }) => {
+                // The process is stuck on this command, replace it so we remain usable.
+                if let Err(e) = self.restart() {
+                    warn!("Failed to restart exiftool after a timeout: {}", e);
                 }
+                Err(ExifToolError::Timeout {
+                    command_args: args.join(" "),
+                    elapsed: start_time.elapsed(),
+                })
             }
+            result => result.map(|(stdout, stderr)| CommandOutput { stdout, stderr }),
         }
-
-        // If stderr contained only warnings or was empty, return the stdout bytes
-        Ok(stdout_bytes)
     }
 
-    /// Reads from stdout until the `exiftool` "{ready}" marker is found.
+    /// Reads from stdout until the `{ready<N>}` marker of command `command_id` is found.
     /// Internal helper function.
-    fn read_response_until_ready(&mut self) -> Result<Vec<u8>, ExifToolError> {
+    ///
+    /// The marker is only accepted at the very end of the output read so far, since exiftool
+    /// prints it last and then waits for the next command. This way file contents that happen
+    /// to contain a marker can't cut the response short.
+    ///
+    /// Returns [`ExifToolError::Timeout`] (without command args) if `deadline` passes first.
+    fn read_response_until_ready(
+        &mut self,
+        command_id: u64,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<u8>, ExifToolError> {
+        let start_time = Instant::now();
         let mut buffer = Vec::new();
-        let ready_markers: &[&[u8]] = &[b"{ready}\n", b"{ready}\r\n"];
+        let marker = ready_marker(command_id);
+        let ready_markers = [format!("{}\n", marker), format!("{}\r\n", marker)];
 
         loop {
-            let mut chunk = [0u8; 4096];
-            let bytes_read = self.stdout.read(&mut chunk)?;
-            if bytes_read == 0 {
-                // EOF before "{ready}" means the process likely terminated.
-                // Try draining stderr one last time to capture potential fatal errors.
-                let stderr_lines = self.drain_stderr().unwrap_or_default();
-                return if !stderr_lines.is_empty() {
-                    Err(ExifToolError::ExifToolProcess {
-                        std_err: stderr_lines.join("\n"),
-                        message: format!(
-                            "Process terminated unexpectedly. Stderr:\n{}",
-                            stderr_lines.join("\n")
-                        ),
-                        command_args: "<unknown - process terminated>".to_string(),
+            let chunk = match recv_until(&self.stdout_receiver, deadline) {
+                Ok(chunk) => chunk,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(ExifToolError::Timeout {
+                        command_args: String::new(),
+                        elapsed: start_time.elapsed(),
                     })
-                } else {
-                    Err(ExifToolError::ProcessTerminated)
-                };
-            }
-            buffer.extend_from_slice(&chunk[..bytes_read]);
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    // EOF before "{ready}" means the process likely terminated.
+                    self.terminated = true;
+                    // Collect stderr one last time to capture potential fatal errors.
+                    let stderr_lines = self.drain_remaining_stderr();
+                    return if !stderr_lines.is_empty() {
+                        Err(ExifToolError::ExifToolProcess {
+                            std_err: stderr_lines.join("\n"),
+                            message: format!(
+                                "Process terminated unexpectedly. Stderr:\n{}",
+                                stderr_lines.join("\n")
+                            ),
+                            command_args: "<unknown - process terminated>".to_string(),
+                        })
+                    } else {
+                        Err(ExifToolError::ProcessTerminated)
+                    };
+                }
+            };
+            buffer.extend_from_slice(&chunk);
 
             // Check all possible markers
-            for marker in ready_markers {
-                if let Some(pos) = buffer.windows(marker.len()).position(|w| w == *marker) {
-                    let data = buffer[..pos].to_vec();
-                    buffer.drain(..pos + marker.len());
-                    return Ok(data);
+            for marker in &ready_markers {
+                if buffer.ends_with(marker.as_bytes()) {
+                    buffer.truncate(buffer.len() - marker.len());
+                    return Ok(buffer);
                 }
             }
         }
     }
 
-    /// Drains the stderr channel, collecting recent error messages.
+    /// Collects the stderr lines of command `command_id`, up to its `-echo4` marker.
     /// Internal helper function.
-    fn drain_stderr(&mut self) -> Result<Vec<String>, ExifToolError> {
-        let mut err_lines = Vec::new();
+    ///
+    /// Lines followed by the marker of an *older* command belonged to that command and are
+    /// discarded, so output is never attributed to the wrong command.
+    ///
+    /// Returns [`ExifToolError::Timeout`] (without command args) if `deadline` passes first.
+    fn read_stderr_until_ready(
+        &mut self,
+        command_id: u64,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<String>, ExifToolError> {
         let start_time = Instant::now();
+        let mut err_lines = Vec::new();
 
-        // First, quickly drain any immediately available messages
         loop {
-            match self.stderr_receiver.try_recv() {
-                Ok(line) => err_lines.push(line),
-                Err(TryRecvError::Empty) => break,
-                Err(TryRecvError::Disconnected) => return Err(ExifToolError::StderrDisconnected),
-            }
-        }
-
-        // Then, poll briefly for any messages that might be slightly delayed
-        while start_time.elapsed() < STDERR_POLL_TIMEOUT {
-            match self.stderr_receiver.try_recv() {
-                Ok(line) => err_lines.push(line),
-                Err(TryRecvError::Empty) => {
-                    // If we already have errors, assume the burst is over.
-                    // If not, sleep briefly and try again.
-                    if !err_lines.is_empty() {
-                        break;
+            match recv_until(&self.stderr_receiver, deadline) {
+                Ok(line) => match parse_ready_marker(&line) {
+                    Some(id) if id == command_id => return Ok(err_lines),
+                    Some(id) => {
+                        debug!("Discarding stderr output of earlier command {}", id);
+                        err_lines.clear();
                     }
-                    thread::sleep(STDERR_POLL_INTERVAL);
+                    None => err_lines.push(line),
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(ExifToolError::Timeout {
+                        command_args: String::new(),
+                        elapsed: start_time.elapsed(),
+                    })
                 }
-                Err(TryRecvError::Disconnected) => {
-                    // If disconnect happens *while* polling, report it,
-                    // but return any errors collected so far.
+                Err(RecvTimeoutError::Disconnected) => {
+                    // If the stream closes before the marker, return what was collected.
                     if err_lines.is_empty() {
                         return Err(ExifToolError::StderrDisconnected);
-                    } else {
-                        warn!("Stderr disconnected during polling after receiving some lines.");
-                        break; // Return collected lines below
                     }
+                    warn!("Stderr disconnected before the end of command output.");
+                    return Ok(err_lines);
                 }
             }
         }
+    }
+
+    /// Collects whatever is left on stderr after the process terminated.
+    /// Internal helper function.
+    fn drain_remaining_stderr(&mut self) -> Vec<String> {
+        let deadline = Instant::now() + STDERR_DRAIN_TIMEOUT;
+        let mut err_lines = Vec::new();
+        while let Ok(line) = recv_until(&self.stderr_receiver, Some(deadline)) {
+            if parse_ready_marker(&line).is_none() {
+                err_lines.push(line);
+            }
+        }
+        err_lines
+    }
+
+    /// Kills the current `exiftool` process and replaces it with a freshly spawned one.
+    /// Internal helper function, used to recover from timeouts.
+    fn restart(&mut self) -> Result<(), ExifToolError> {
+        // Kill first, so the old instance's Drop doesn't wait on a hung process.
+        if let Err(e) = self.child.kill() {
+            warn!("Failed to kill hung exiftool process: {}", e);
+        }
+        self.terminated = true;
+
+        let mut fresh = Self::spawn(self.builder.clone())?;
+        fresh.timeout = self.timeout;
+        fresh.backup_policy = self.backup_policy;
+        fresh.sidecar_naming = self.sidecar_naming;
+        drop(std::mem::replace(self, fresh));
+        Ok(())
+    }
 
-        Ok(err_lines)
+    /// Returns `true` if the `exiftool` process is still running.
+    ///
+    /// A process is considered dead once its stdout has been closed or the child has exited.
+    /// Internal helper function, used by [`crate::ExifToolPool`] to discard dead workers.
+    pub(crate) fn is_alive(&mut self) -> bool {
+        !self.terminated && matches!(self.child.try_wait(), Ok(None))
     }
 
     /// Sends the command to gracefully close the persistent exiftool process.
@@ -432,21 +755,98 @@ impl ExifTool {
         let mut cmd_args = vec!["-json"];
         cmd_args.extend_from_slice(args);
         let output_bytes = self.execute_raw(&cmd_args)?;
-        // Handle empty output gracefully - ExifTool might return empty output for
-        // certain commands or errors that weren't caught via stderr.
-        if output_bytes.is_empty() {
-            // Or return Ok(Value::Null) or Ok(Value::Array(vec![])) ?
-            return Err(ExifToolError::UnexpectedFormat {
-                path: args
+        parse_json_output(args, &output_bytes)
+    }
+
+    /// Executes a typed [`Command`] and returns the raw byte output.
+    ///
+    /// See [`ExifTool::execute_raw`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`ExifTool::execute_raw`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{Command, ExifTool, ExifToolError};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let output = et.execute(&Command::new().file("image.jpg").tag("Make").arg("-S"))?;
+    /// println!("{}", String::from_utf8_lossy(&output)); // "Make: Canon"
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute(&mut self, command: &Command) -> Result<Vec<u8>, ExifToolError> {
+        let args = command.to_args();
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.execute_raw(&arg_refs)
+    }
+
+    /// Executes a typed [`Command`] with `-json` and returns one [`Value`] per processed file.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`ExifTool::json_execute`]. Returns
+    /// [`ExifToolError::UnexpectedFormat`] if the output is not a JSON array, which includes
+    /// the case where no file matched the command.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{Command, ExifTool, ExifToolError, GroupLevel};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let command = Command::new().files(["a.jpg", "b.jpg"]).group(GroupLevel::Specific);
+    /// let results = et.json_command(&command)?;
+    /// assert_eq!(results.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn json_command(&mut self, command: &Command) -> Result<Vec<Value>, ExifToolError> {
+        let args = command.to_args();
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        match self.json_execute(&arg_refs)? {
+            Value::Array(array) => Ok(array),
+            _ => Err(ExifToolError::UnexpectedFormat {
+                path: command
+                    .get_files()
                     .iter()
-                    .find(|a| !a.starts_with('-'))
-                    .unwrap_or(&"<unknown>")
-                    .to_string(),
-                command_args: cmd_args.join(" "),
-            });
+                    .map(|p| p.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                command_args: format!("-json {}", args.join(" ")),
+            }),
         }
-        let value: Value = serde_json::from_slice(&output_bytes)?;
-        Ok(value)
+    }
+
+    /// Executes a typed [`Command`] with `-json` and deserializes each file's output into `T`.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`ExifTool::json_command`], and
+    /// [`ExifToolError::Json`] if deserialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::g2::ExifData;
+    /// use exiftool::{Command, ExifTool, ExifToolError, GroupLevel};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let command = Command::new().file("image.jpg").group(GroupLevel::Category);
+    /// let data: Vec<ExifData> = et.read_command(&command)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_command<T: DeserializeOwned>(
+        &mut self,
+        command: &Command,
+    ) -> Result<Vec<T>, ExifToolError> {
+        self.json_command(command)?
+            .into_iter()
+            .map(|value| serde_path_to_error::deserialize(value).map_err(ExifToolError::from))
+            .collect()
     }
 
     // --- Reading Metadata ---
@@ -527,23 +927,28 @@ impl ExifTool {
         }
     }
 
-    /// Reads metadata for a single file, returning a raw [`Value`].
+    /// Reads metadata for multiple files, returning a separate result for every file.
     ///
-    /// Runs `exiftool -json {extra_args...} {file_path}`.
-    /// Since `exiftool -json` typically returns an array even for a single file,
-    /// this method extracts the *first* element from that array.
+    /// Unlike [`ExifTool::json_batch`], an error for one file (e.g. a missing path) does not
+    /// discard the metadata of the others. Results are returned in the same order as
+    /// `file_paths`, each paired with its input path. Output is matched to inputs by the
+    /// `SourceFile` field `exiftool` adds to every JSON object.
     ///
-    /// # Arguments
+    /// Per-file errors are:
+    /// * [`ExifToolError::FileNotFound`] if the file does not exist.
+    /// * [`ExifToolError::ExifToolProcess`] if `exiftool` reported another error for the file,
+    ///   either on stderr or as an `Error` tag in its JSON output (e.g. `Unknown file type`).
+    /// * [`ExifToolError::UnexpectedFormat`] if `exiftool` returned nothing for the file, e.g.
+    ///   because an `-if` condition excluded it.
     ///
-    /// * `file_path`: The [`Path`] to the file to process.
-    /// * `extra_args`: Additional arguments like `-g1`, `-common`, etc.
+    /// Pass files, not directories: results for files found inside a directory can't be
+    /// matched to an input path and are dropped.
     ///
     /// # Errors
     ///
-    /// Returns an [`ExifToolError`] if the command or parsing fails. See [`ExifTool::json_execute`]
-    /// for potential errors. Additionally, returns [`ExifToolError::UnexpectedFormat`] if
-    /// `exiftool` returns an empty array (which might happen if the file wasn't processed
-    /// successfully, even if no stderr error occurred) or if the result is not an array.
+    /// The outer `Result` only fails if the command as a whole fails, e.g. with
+    /// [`ExifToolError::Io`], [`ExifToolError::ProcessTerminated`] or [`ExifToolError::Json`]
+    /// if the output is not valid JSON.
     ///
     /// # Example
     ///
@@ -553,22 +958,195 @@ impl ExifTool {
     ///
     /// # fn main() -> Result<(), ExifToolError> {
     /// let mut exiftool = ExifTool::new()?;
-    /// let path = Path::new("data/image.jpg");
-    ///
-    /// // Get common tags (-common) grouped by family 1 (-g1)
-    /// let result = exiftool.json(path, &["-g1", "-common"])?;
+    /// let paths = [Path::new("image1.jpg"), Path::new("missing.jpg")];
     ///
-    /// println!("Metadata: {}", result);
-    /// // Example output: {"SourceFile": "data/image.jpg", "EXIF": {...}, "XMP": {...}, ...}
+    /// for (path, result) in exiftool.json_batch_results(paths, &["-Make"])? {
+    ///     match result {
+    ///         Ok(metadata) => println!("{}: {}", path.display(), metadata["Make"]),
+    ///         Err(e) => println!("{}: {}", path.display(), e),
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub fn json(&mut self, file_path: &Path, extra_args: &[&str]) -> Result<Value, ExifToolError> {
-        let path_str = file_path.to_string_lossy();
-        let mut args = extra_args.to_vec();
-        args.push(path_str.as_ref());
-
-        let results = self.json_batch(std::iter::once(file_path), extra_args)?;
+    pub fn json_batch_results<I, P>(
+        &mut self,
+        file_paths: I,
+        extra_args: &[&str],
+    ) -> Result<Vec<BatchResult>, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let paths: Vec<PathBuf> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let path_strs: Vec<String> = paths
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        let mut args = vec!["-json"];
+        args.extend_from_slice(extra_args);
+        args.extend(path_strs.iter().map(String::as_str));
+
+        let output = self.execute_with_stderr(&args)?;
+        split_batch_output(paths, &args, output)
+    }
+
+    /// Lazily reads and deserializes the metadata of a large number of files.
+    ///
+    /// Unlike [`ExifTool::json_batch`], which runs a single command and buffers the output for
+    /// all files, this sends the paths to `exiftool` in chunks of
+    /// [`DEFAULT_CHUNK_SIZE`](crate::DEFAULT_CHUNK_SIZE) (configurable with
+    /// [`MetadataIter::chunk_size`]) and yields one `Result<T>` per file, in input order, even
+    /// when a whole chunk fails. `file_paths` is also consumed lazily, one chunk at a time;
+    /// memory use is bounded by the output of one chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths`: The files to read. Pass files, not directories, see
+    ///   [`ExifTool::json_batch_results`].
+    /// * `extra_args`: Additional arguments passed to every chunk's command, e.g. `-g2`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::g2::ExifData;
+    /// use exiftool::{ExifTool, ExifToolError};
+    /// use std::path::PathBuf;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// # let archive: Vec<PathBuf> = vec![];
+    /// let mut exiftool = ExifTool::new()?;
+    ///
+    /// for result in exiftool
+    ///     .read_metadata_iter::<ExifData, _, _>(&archive, &["-g2"])
+    ///     .chunk_size(500)
+    /// {
+    ///     match result {
+    ///         Ok(data) => println!("{:?}", data.camera),
+    ///         Err(e) => eprintln!("Skipping file: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_metadata_iter<T, I, P>(
+        &mut self,
+        file_paths: I,
+        extra_args: &[&str],
+    ) -> MetadataIter<'_, T, I::IntoIter>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        MetadataIter::new(self, file_paths.into_iter(), extra_args)
+    }
+
+    /// Reads metadata for multiple files like [`ExifTool::json_batch`], and also returns the
+    /// warnings reported for them.
+    ///
+    /// Warnings are collected from stderr and from the `Warning` tag `exiftool` includes in a
+    /// file's JSON output, e.g. `[minor] Bad MakerNotes offset`. Each [`Warning`] names the file
+    /// it belongs to when known.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`ExifTool::json_batch`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut exiftool = ExifTool::new()?;
+    /// let paths = [Path::new("image1.jpg"), Path::new("image2.jpg")];
+    /// let result = exiftool.json_batch_with_diagnostics(paths, &[])?;
+    ///
+    /// for warning in result.warnings.iter().filter(|w| !w.minor) {
+    ///     println!("Check {:?}: {}", warning.file, warning.message);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn json_batch_with_diagnostics<I, P>(
+        &mut self,
+        file_paths: I,
+        extra_args: &[&str],
+    ) -> Result<WithWarnings<Vec<Value>>, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let path_strs: Vec<String> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect();
+
+        if path_strs.is_empty() {
+            return Err(ExifToolError::UnexpectedFormat {
+                path: "".to_string(),
+                command_args: extra_args.join(","),
+            });
+        }
+
+        let mut args = vec!["-json"];
+        args.extend_from_slice(extra_args);
+        args.extend(path_strs.iter().map(String::as_str));
+
+        let output = self.execute_with_diagnostics(&args)?;
+        json_batch_warnings(&args, output, &path_strs)
+    }
+
+    /// Reads metadata for a single file, returning a raw [`Value`].
+    ///
+    /// Runs `exiftool -json {extra_args...} {file_path}`.
+    /// Since `exiftool -json` typically returns an array even for a single file,
+    /// this method extracts the *first* element from that array.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path`: The [`Path`] to the file to process.
+    /// * `extra_args`: Additional arguments like `-g1`, `-common`, etc.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ExifToolError`] if the command or parsing fails. See [`ExifTool::json_execute`]
+    /// for potential errors. Additionally, returns [`ExifToolError::UnexpectedFormat`] if
+    /// `exiftool` returns an empty array (which might happen if the file wasn't processed
+    /// successfully, even if no stderr error occurred) or if the result is not an array.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut exiftool = ExifTool::new()?;
+    /// let path = Path::new("data/image.jpg");
+    ///
+    /// // Get common tags (-common) grouped by family 1 (-g1)
+    /// let result = exiftool.json(path, &["-g1", "-common"])?;
+    ///
+    /// println!("Metadata: {}", result);
+    /// // Example output: {"SourceFile": "data/image.jpg", "EXIF": {...}, "XMP": {...}, ...}
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn json(&mut self, file_path: &Path, extra_args: &[&str]) -> Result<Value, ExifToolError> {
+        let path_str = file_path.to_string_lossy();
+        let mut args = extra_args.to_vec();
+        args.push(path_str.as_ref());
+
+        let results = self.json_batch(std::iter::once(file_path), extra_args)?;
 
         results.into_iter().next().ok_or_else(|| {
             // This may happen if exiftool had an error (like file not found)
@@ -863,43 +1441,119 @@ impl ExifTool {
         file_path: &Path,
         tag: &str,
     ) -> Result<T, ExifToolError> {
-        // Step 1: Attempt to get the JSON value for the tag
-        let value_result = self.json_tag(file_path, tag);
-
-        match value_result {
-            // Case 1: Tag found, value exists. Try to deserialize it directly.
-            Ok(value) => {
-                serde_json::from_value(value).map_err(|e| ExifToolError::TagDeserialization {
-                    path: file_path.to_path_buf(),
-                    tag: tag.to_string(),
-                    error: e,
-                })
-            }
+        deserialize_tag(file_path, tag, self.json_tag(file_path, tag))
+    }
 
-            // Case 2: Tag specifically not found by the underlying method.
-            // Now we need to check if T expects an Option.
-            Err(ExifToolError::TagNotFound { .. }) => {
-                // Try to deserialize `Value::Null`. This only works if T can handle `null`
-                // (most commonly, if T is Option<Inner>).
-                match serde_json::from_value(Value::Null) {
-                    // If deserializing `null` succeeds, it implies T is Option-like.
-                    // The result `val` will be the `None` variant wrapped in T (which is Option<Inner>).
-                    Ok(val) => Ok(val),
-
-                    // If deserializing `null` fails, it means T was *not* expecting an Option
-                    // (e.g., T is String, u32). In this situation, the original TagNotFound
-                    // error is the correct one to surface.
-                    Err(_) => Err(ExifToolError::TagNotFound {
-                        // Reconstruct the specific error
-                        path: file_path.to_path_buf(),
-                        tag: tag.to_string(),
-                    }),
-                }
-            }
+    /// Reads tags with both their raw (`-n`) and print-converted values in one call.
+    ///
+    /// Runs `exiftool -json -l {-TAG...} {file_path}`, or reads all tags if `tags` is empty.
+    /// This avoids reading a file twice when a value should be shown to users (`"Rotate 90 CW"`)
+    /// and also used in computations (`6`).
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path`: The [`Path`] to the file.
+    /// * `tags`: Tag names without the leading `-`. Empty to read all tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ExifToolError`] if the command fails, see [`ExifTool::json`]. Missing tags
+    /// are left out of the map rather than returning an error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut exiftool = ExifTool::new()?;
+    /// let values = exiftool.read_tag_values(Path::new("photo.jpg"), &["Orientation", "Make"])?;
+    ///
+    /// if let Some(orientation) = values.get("Orientation") {
+    ///     // Prints e.g. "Orientation: Rotate 90 CW (6)"
+    ///     println!("Orientation: {} ({})", orientation, orientation.raw);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_tag_values(
+        &mut self,
+        file_path: &Path,
+        tags: &[&str],
+    ) -> Result<BTreeMap<String, TagValue>, ExifToolError> {
+        let args = tag_value_args(tags);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        Ok(parse_long_json(self.json(file_path, &args)?))
+    }
 
-            // Case 3: Any other error occurred while fetching the tag (IO, process error, etc.)
-            Err(e) => Err(e), // Propagate the underlying error
-        }
+    /// Reads a single tag's raw and print-converted value, see [`ExifTool::read_tag_values`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExifToolError::TagNotFound`] if the file doesn't have the tag, or the errors
+    /// of [`ExifTool::json`].
+    pub fn read_tag_value(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+    ) -> Result<TagValue, ExifToolError> {
+        self.read_tag_values(file_path, &[tag])?
+            .remove(tag)
+            .ok_or_else(|| ExifToolError::TagNotFound {
+                path: file_path.to_path_buf(),
+                tag: tag.to_string(),
+            })
+    }
+
+    /// Like [`ExifTool::read_tag`], but reads the given [`Representation`] of the value.
+    ///
+    /// [`Representation::Raw`] adds `-n` to the command, so e.g. `Orientation` can be read as
+    /// a number without disabling print conversion for other calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ExifTool::read_tag`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError, Representation};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut exiftool = ExifTool::new()?;
+    /// let path = Path::new("photo.jpg");
+    ///
+    /// let orientation: Option<u16> = exiftool.read_tag_as(path, "Orientation", Representation::Raw)?;
+    /// let label: Option<String> =
+    ///     exiftool.read_tag_as(path, "Orientation", Representation::Printed)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_tag_as<T: DeserializeOwned>(
+        &mut self,
+        file_path: &Path,
+        tag: &str,
+        representation: Representation,
+    ) -> Result<T, ExifToolError> {
+        let value = match representation {
+            Representation::Printed => self.json_tag(file_path, tag),
+            Representation::Raw => {
+                let tag_arg = format!("-{}", tag);
+                self.json(file_path, &["-n", &tag_arg])
+                    .and_then(|metadata| {
+                        metadata
+                            .get(tag)
+                            .cloned()
+                            .ok_or_else(|| ExifToolError::TagNotFound {
+                                path: file_path.to_path_buf(),
+                                tag: tag.to_string(),
+                            })
+                    })
+            }
+        };
+        deserialize_tag(file_path, tag, value)
     }
 
     /// Reads a binary tag (like `ThumbnailImage`, `PreviewImage`) as raw bytes (`Vec<u8>`).
@@ -969,6 +1623,124 @@ impl ExifTool {
         Ok(bytes)
     }
 
+    // --- Reading In-Memory Data ---
+
+    /// Reads metadata from file contents held in memory, returning a raw [`Value`].
+    ///
+    /// Equivalent to [`ExifTool::json`] for data that isn't stored in a file, e.g. an upload.
+    /// The persistent process reads its commands from stdin, so the data is piped into a
+    /// separate, short-lived `exiftool` process (`exiftool -json {extra_args...} -`) configured
+    /// like this instance. This adds the startup cost of `exiftool` to every call.
+    ///
+    /// Since there is no file, file-system tags like `FileName` or `FileModifyDate` are absent
+    /// and `SourceFile` is `"-"`.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExifToolError::ExifToolNotFound`] if the process can't be started.
+    /// * [`ExifToolError::ExifToolProcess`] if `exiftool` reports an error, e.g.
+    ///   `Unknown file type` for unsupported data.
+    /// * [`ExifToolError::Timeout`] if the configured timeout elapses.
+    /// * [`ExifToolError::Io`] or [`ExifToolError::Json`] if communication or parsing fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let exiftool = ExifTool::new()?;
+    /// let upload: Vec<u8> = std::fs::read("image.jpg")?;
+    /// let metadata = exiftool.json_from_bytes(&upload, &["-Make"])?;
+    /// println!("Make: {}", metadata["Make"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn json_from_bytes(
+        &self,
+        data: &[u8],
+        extra_args: &[&str],
+    ) -> Result<Value, ExifToolError> {
+        self.json_from_reader(data, extra_args)
+    }
+
+    /// Reads metadata from a [`Read`] stream, returning a raw [`Value`].
+    ///
+    /// Like [`ExifTool::json_from_bytes`], but streams the data into `exiftool` instead of
+    /// requiring it in memory. `exiftool` stops reading once it has found all metadata, so the
+    /// reader may not be consumed completely.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::json_from_bytes`], plus [`ExifToolError::Io`] if reading fails.
+    pub fn json_from_reader<R: Read + Send>(
+        &self,
+        reader: R,
+        extra_args: &[&str],
+    ) -> Result<Value, ExifToolError> {
+        let mut args = vec!["-json"];
+        args.extend_from_slice(extra_args);
+        args.push("-");
+
+        let output = run_oneshot(&self.builder, &args, reader, self.timeout)?;
+        split_batch_output(vec![PathBuf::from("-")], &args, output)?
+            .pop()
+            .map(|(_, result)| result)
+            .unwrap_or_else(|| {
+                Err(ExifToolError::UnexpectedFormat {
+                    path: "-".to_string(),
+                    command_args: args.join(" "),
+                })
+            })
+    }
+
+    /// Reads metadata from file contents held in memory and deserializes it into `T`.
+    ///
+    /// See [`ExifTool::json_from_bytes`] and [`ExifTool::read_metadata`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::json_from_bytes`], plus [`ExifToolError::Deserialization`] if the
+    /// metadata doesn't match `T`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::g2::ExifData;
+    /// use exiftool::{ExifTool, ExifToolError};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let exiftool = ExifTool::new()?;
+    /// let upload: Vec<u8> = std::fs::read("image.jpg")?;
+    /// let data: ExifData = exiftool.read_metadata_from_bytes(&upload, &["-g2"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_metadata_from_bytes<T: DeserializeOwned>(
+        &self,
+        data: &[u8],
+        extra_args: &[&str],
+    ) -> Result<T, ExifToolError> {
+        self.read_metadata_from_reader(data, extra_args)
+    }
+
+    /// Reads metadata from a [`Read`] stream and deserializes it into `T`.
+    ///
+    /// See [`ExifTool::json_from_reader`] and [`ExifTool::read_metadata`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::json_from_reader`], plus [`ExifToolError::Deserialization`] if the
+    /// metadata doesn't match `T`.
+    pub fn read_metadata_from_reader<T: DeserializeOwned, R: Read + Send>(
+        &self,
+        reader: R,
+        extra_args: &[&str],
+    ) -> Result<T, ExifToolError> {
+        let value = self.json_from_reader(reader, extra_args)?;
+        serde_path_to_error::deserialize(value).map_err(ExifToolError::from)
+    }
+
     // --- Writing Metadata ---
 
     /// Writes a value (converted to a string) to a specific tag in a file's metadata.
@@ -977,9 +1749,13 @@ impl ExifTool {
     /// The `value` provided will be converted to its string representation using [`ToString`]
     /// before being passed to `exiftool`.
     ///
+    /// Returns a [`WriteReport`] parsed from `exiftool`'s summary, which tells whether the file
+    /// was updated or unchanged, and contains any warnings.
+    ///
     /// **Warning:** By default, `exiftool` creates a backup file by renaming the original
-    /// file to `{filename}_original`. To prevent this and modify the file in place,
-    /// include `"-overwrite_original"` in the `extra_args`. Use with caution.
+    /// file to `{filename}_original`. To prevent this, set a different [`BackupPolicy`] with
+    /// [`ExifTool::set_backup_policy`] (or pass `"-overwrite_original"` in `extra_args`).
+    /// Use with caution.
     ///
     /// # Arguments
     ///
@@ -996,6 +1772,8 @@ impl ExifTool {
     /// Returns an [`ExifToolError`] on failure:
     /// * Errors from the underlying [`ExifTool::execute_raw`] call (e.g., [`ExifToolError::Io`],
     ///   [`ExifToolError::FileNotFound`], [`ExifToolError::ExifToolProcess`]).
+    /// * [`ExifToolError::NoFilesUpdated`]: If the file was neither updated nor found unchanged
+    ///   and `exiftool` reported no error, e.g. because the tag isn't writable.
     ///
     /// # Example
     ///
@@ -1047,7 +1825,7 @@ impl ExifTool {
         tag: &str,
         value: T,
         extra_args: &[&str],
-    ) -> Result<(), ExifToolError> {
+    ) -> Result<WriteReport, ExifToolError> {
         let value_str = value.to_string();
         // Format the core argument: -TAG=VALUE
         let tag_arg = format!("-{}={}", tag, value_str);
@@ -1056,13 +1834,13 @@ impl ExifTool {
 
         // Assemble arguments: tag assignment first, then extra args, then file path
         let mut args = vec![tag_arg.as_str()];
+        args.extend(self.backup_policy.arg());
         args.extend_from_slice(extra_args);
         args.push(path_str.as_ref());
 
-        // Execute the command. The output (usually like "1 image files updated") is ignored.
-        // Errors are checked via stderr within execute_raw.
-        self.execute_raw(&args)?;
-        Ok(())
+        // Execute the command and parse the summary (usually "1 image files updated").
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
     }
 
     /// Writes raw binary data to a specific tag (e.g., `ThumbnailImage`).
@@ -1072,8 +1850,10 @@ impl ExifTool {
     /// and then telling `exiftool` to read the tag's value from that file using the
     /// `-TAG<=TEMPFILE` syntax.
     ///
+    /// Returns a [`WriteReport`], like [`ExifTool::write_tag`].
+    ///
     /// **Warning:** By default, `exiftool` creates a backup file (`{filename}_original`).
-    /// To prevent this, include `"-overwrite_original"` in `extra_args`.
+    /// To prevent this, set a different [`BackupPolicy`] with [`ExifTool::set_backup_policy`].
     ///
     /// # Arguments
     ///
@@ -1092,6 +1872,7 @@ impl ExifTool {
     ///   communication with the process fails.
     /// * Errors from the underlying [`ExifTool::execute_raw`] call (e.g., [`ExifToolError::FileNotFound`],
     ///   [`ExifToolError::ExifToolProcess`]).
+    /// * [`ExifToolError::NoFilesUpdated`]: If the file was neither updated nor found unchanged.
     ///
     /// # Example
     ///
@@ -1137,7 +1918,7 @@ impl ExifTool {
         tag: &str,
         data: D,
         extra_args: &[&str],
-    ) -> Result<(), ExifToolError> {
+    ) -> Result<WriteReport, ExifToolError> {
         // Create a temporary file to hold the binary data
         let mut temp_file = NamedTempFile::new()?;
         temp_file.write_all(data.as_ref())?;
@@ -1150,29 +1931,807 @@ impl ExifTool {
 
         let file_path_str = file_path.to_string_lossy();
         let mut args = vec![tag_arg.as_str()];
+        args.extend(self.backup_policy.arg());
         args.extend_from_slice(extra_args);
         args.push(file_path_str.as_ref());
 
-        // Execute and ignore output. temp_file is dropped (and deleted) after this scope.
-        let _ = self.execute_raw(&args)?;
-        Ok(())
+        // temp_file is dropped (and deleted) after this scope.
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
     }
-}
-
-impl Drop for ExifTool {
-    /// Attempts to gracefully close the `exiftool` process and then kills it
-    /// if it hasn't terminated after a short grace period (implicit in `kill`).
-    fn drop(&mut self) {
-        // 1. Attempt graceful shutdown by sending exit commands.
-        if let Err(e) = self.close() {
-            // Log if closing failed, but proceed to kill anyway.
-            warn!("Failed to send close command to exiftool process: {}", e);
-        }
 
-        // 2. Kill the process. This ensures cleanup even if graceful shutdown fails
-        //    or hangs. `kill()` on Unix sends SIGKILL; on Windows, TerminateProcess.
-        if let Err(e) = self.child.kill() {
-            // Log if killing failed (e.g., process already dead).
+    /// Applies all assignments of a [`WriteSet`] to one or more files in a single command.
+    ///
+    /// Runs `exiftool {assignments...} [backup option] {extra_args...} {file_paths...}`. Each file is rewritten
+    /// once with all assignments, so it's either fully updated or left untouched.
+    ///
+    /// Like [`ExifTool::write_tag`], `exiftool` keeps a backup of each modified file as
+    /// `{filename}_original` unless a different [`BackupPolicy`] is set.
+    ///
+    /// An empty `writes` set or an empty list of files doesn't run a command and returns an
+    /// empty report.
+    ///
+    /// When some of the files can't be written (e.g. because they don't exist), the others are
+    /// still updated and the failures are counted in [`WriteReport::failed`].
+    ///
+    /// # Errors
+    ///
+    /// Only fails if no file was updated or found unchanged: with the error reported by
+    /// `exiftool`, e.g. [`ExifToolError::FileNotFound`] or [`ExifToolError::ExifToolProcess`],
+    /// or with [`ExifToolError::NoFilesUpdated`] if it didn't report one. Also returns
+    /// communication errors like [`ExifToolError::Io`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError, WriteSet};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let writes = WriteSet::new()
+    ///     .set("Title", "Sunset")
+    ///     .set("Artist", "Jane Doe")
+    ///     .append("Keywords", "beach")
+    ///     .set("GPSLatitude", 52.37)
+    ///     .set("GPSLatitudeRef", "N");
+    ///
+    /// let report = et.write_tags([Path::new("a.jpg"), Path::new("b.jpg")], &writes, &[])?;
+    /// assert_eq!(report.updated + report.unchanged, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_tags<I, P>(
+        &mut self,
+        file_paths: I,
+        writes: &WriteSet,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let path_strs: Vec<String> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect();
+        if writes.is_empty() || path_strs.is_empty() {
+            return Ok(WriteReport::default());
+        }
+
+        let write_args = writes.to_args();
+        let mut args: Vec<&str> = write_args.iter().map(String::as_str).collect();
+        args.extend(self.backup_policy.arg());
+        args.extend_from_slice(extra_args);
+        args.extend(path_strs.iter().map(String::as_str));
+
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Restores files from the `{filename}_original` backups left by earlier writes
+    /// (`-restore_original`), undoing all changes since the backup was made.
+    ///
+    /// Returns the number of files restored. Files without a backup are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Only fails if no file was restored and `exiftool` reported an error, e.g.
+    /// [`ExifToolError::FileNotFound`]. Also returns communication errors like
+    /// [`ExifToolError::Io`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// et.write_tag(Path::new("image.jpg"), "Artist", "Jane Doe", &[])?;
+    ///
+    /// // Changed our mind: bring back `image.jpg_original`.
+    /// let restored = et.restore_original([Path::new("image.jpg")])?;
+    /// assert_eq!(restored, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn restore_original<I, P>(&mut self, file_paths: I) -> Result<usize, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.originals_command("-restore_original", file_paths)
+    }
+
+    /// Deletes the `{filename}_original` backups of files (`-delete_original!`), keeping the
+    /// written files.
+    ///
+    /// Returns the number of backups deleted. Files without a backup are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::restore_original`].
+    pub fn delete_original<I, P>(&mut self, file_paths: I) -> Result<usize, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.originals_command("-delete_original!", file_paths)
+    }
+
+    /// Runs `-restore_original` or `-delete_original!` on the given files.
+    fn originals_command<I, P>(
+        &mut self,
+        option: &str,
+        file_paths: I,
+    ) -> Result<usize, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let path_strs: Vec<String> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect();
+        if path_strs.is_empty() {
+            return Ok(0);
+        }
+
+        let mut args = vec![option];
+        args.extend(path_strs.iter().map(String::as_str));
+        let output = self.execute_with_stderr(&args)?;
+        originals_count(&args, &output)
+    }
+
+    /// Copies metadata from one file to another (`-tagsFromFile`).
+    ///
+    /// Runs `exiftool -tagsFromFile {src} {selection...} [backup option] {extra_args...} {dst}`.
+    /// Tags are copied with their print-converted values and, unless redirected, written to
+    /// their preferred group in `dst` (select `"all:all"` with [`CopySelection::tag`] to keep
+    /// the source groups).
+    ///
+    /// Returns a [`WriteReport`] for `dst`. The [`BackupPolicy`] applies to `dst`; `src` is
+    /// never modified.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::write_tags`], e.g. [`ExifToolError::NoFilesUpdated`] if none of the
+    /// selected tags exist in `src`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{CopySelection, ExifTool, ExifToolError};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    ///
+    /// // Everything from the RAW file to its exported JPEG.
+    /// let all = CopySelection::all();
+    /// et.copy_tags(Path::new("IMG_0001.CR2"), Path::new("IMG_0001.jpg"), &all, &[])?;
+    ///
+    /// // Only GPS and dates from a sidecar.
+    /// let gps_and_dates = CopySelection::all().tag("GPS:all").tag("AllDates");
+    /// et.copy_tags(Path::new("IMG_0002.xmp"), Path::new("IMG_0002.jpg"), &gps_and_dates, &[])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_tags(
+        &mut self,
+        src: &Path,
+        dst: &Path,
+        selection: &CopySelection,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError> {
+        let src_str = src.to_string_lossy();
+        let dst_str = dst.to_string_lossy();
+        let selection_args = selection.to_args();
+        let args = copy_args(
+            &src_str,
+            &dst_str,
+            &selection_args,
+            self.backup_policy.arg(),
+            extra_args,
+        );
+
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Removes metadata from a file, e.g. before publishing it.
+    ///
+    /// Shorthand for [`ExifTool::strip_metadata_batch`] with a single file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError, StripPolicy};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// et.strip_metadata(Path::new("upload.jpg"), &StripPolicy::PrivacyOnly, &[])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn strip_metadata(
+        &mut self,
+        file_path: &Path,
+        policy: &StripPolicy,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError> {
+        self.strip_metadata_batch([file_path], policy, extra_args)
+    }
+
+    /// Removes metadata from several files in a single command.
+    ///
+    /// Runs `exiftool {policy args...} [backup option] {extra_args...} {file_paths...}`. Which
+    /// tags are removed is described by the [`StripPolicy`]. Note that with the default
+    /// [`BackupPolicy::KeepOriginal`] the unstripped originals stay next to the files.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::write_tags`].
+    pub fn strip_metadata_batch<I, P>(
+        &mut self,
+        file_paths: I,
+        policy: &StripPolicy,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let path_strs: Vec<String> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_string_lossy().into_owned())
+            .collect();
+        let policy_args = policy.to_args();
+        if path_strs.is_empty() || policy_args.is_empty() {
+            return Ok(WriteReport::default());
+        }
+
+        let mut args: Vec<&str> = policy_args.iter().map(String::as_str).collect();
+        args.extend(self.backup_policy.arg());
+        args.extend_from_slice(extra_args);
+        args.extend(path_strs.iter().map(String::as_str));
+
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Creates an XMP sidecar next to a file with the file's metadata translated to XMP,
+    /// named by [`ExifTool::sidecar_naming`], e.g. `IMG_0001.xmp` for `IMG_0001.CR2`
+    /// (`-o %d%f.xmp`).
+    ///
+    /// Returns a [`WriteReport`] with the sidecar counted in [`WriteReport::created`].
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ExifToolError::ExifToolProcess`] if the sidecar already exists (use
+    /// [`ExifTool::sync_to_sidecar`] to update it), and otherwise like
+    /// [`ExifTool::write_tags`].
+    pub fn create_sidecar(
+        &mut self,
+        file_path: &Path,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError> {
+        let path_str = file_path.to_string_lossy();
+        let mut args = vec!["-o", self.sidecar_naming.format()];
+        args.extend_from_slice(extra_args);
+        args.push(path_str.as_ref());
+
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Copies a file's metadata to its XMP sidecar, creating the sidecar if it doesn't exist
+    /// yet (see [`ExifTool::create_sidecar`]).
+    ///
+    /// Tags are written to their XMP equivalents; tags without one are skipped. The sidecar
+    /// is always overwritten in place (`-overwrite_original`), regardless of the
+    /// [`BackupPolicy`], so no `.xmp_original` backups pile up next to it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::copy_tags`].
+    pub fn sync_to_sidecar(
+        &mut self,
+        file_path: &Path,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError> {
+        let sidecar = self.sidecar_naming.path(file_path);
+        if !sidecar.exists() {
+            return self.create_sidecar(file_path, extra_args);
+        }
+
+        let src_str = file_path.to_string_lossy();
+        let dst_str = sidecar.to_string_lossy();
+        let selection_args = CopySelection::all().to_args();
+        let args = copy_args(
+            &src_str,
+            &dst_str,
+            &selection_args,
+            BackupPolicy::OverwriteOriginal.arg(),
+            extra_args,
+        );
+
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Copies the XMP tags of a file's sidecar into the file itself, e.g. to embed edits
+    /// made in another application.
+    ///
+    /// The [`BackupPolicy`] applies to the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExifToolError::FileNotFound`] if there is no sidecar, without running
+    /// `exiftool`, and otherwise fails like [`ExifTool::copy_tags`].
+    pub fn sync_from_sidecar(
+        &mut self,
+        file_path: &Path,
+        extra_args: &[&str],
+    ) -> Result<WriteReport, ExifToolError> {
+        let sidecar = self.sidecar_naming.path(file_path);
+        let src_str = sidecar.to_string_lossy();
+        let dst_str = file_path.to_string_lossy();
+        let selection_args = CopySelection::all().tag("XMP:all").to_args();
+        let args = copy_args(
+            &src_str,
+            &dst_str,
+            &selection_args,
+            self.backup_policy.arg(),
+            extra_args,
+        );
+
+        if !sidecar.exists() {
+            return Err(ExifToolError::FileNotFound {
+                path: sidecar.clone(),
+                command_args: args.join(" "),
+            });
+        }
+        let output = self.execute_with_stderr(&args)?;
+        WriteReport::from_output(&args, &output)
+    }
+
+    /// Reads a file's metadata merged with the XMP tags of its sidecar, if it has one.
+    ///
+    /// Runs [`ExifTool::json`] for the file and `-XMP:all` for the sidecar. Tags present in
+    /// both are resolved by `precedence`. Without a sidecar, this returns the same as
+    /// [`ExifTool::json`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::json`], for either file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError, SidecarPrecedence};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// // The rating set in the DAM (IMG_0001.xmp) wins over the camera's.
+    /// let metadata =
+    ///     et.json_with_sidecar(Path::new("IMG_0001.CR2"), SidecarPrecedence::Sidecar, &[])?;
+    /// println!("Rating: {}", metadata["Rating"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn json_with_sidecar(
+        &mut self,
+        file_path: &Path,
+        precedence: SidecarPrecedence,
+        extra_args: &[&str],
+    ) -> Result<Value, ExifToolError> {
+        let embedded = self.json(file_path, extra_args)?;
+        let sidecar = self.sidecar_naming.path(file_path);
+        if !sidecar.exists() {
+            return Ok(embedded);
+        }
+
+        let mut sidecar_args = vec!["-XMP:all"];
+        sidecar_args.extend_from_slice(extra_args);
+        let sidecar = self.json(&sidecar, &sidecar_args)?;
+        Ok(merge_sidecar(embedded, sidecar, precedence))
+    }
+
+    /// Reads a file's metadata merged with its sidecar and deserializes it into `T`.
+    ///
+    /// See [`ExifTool::json_with_sidecar`] and [`ExifTool::read_metadata`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExifTool::json_with_sidecar`], plus [`ExifToolError::Deserialization`].
+    pub fn read_metadata_with_sidecar<T: DeserializeOwned>(
+        &mut self,
+        file_path: &Path,
+        precedence: SidecarPrecedence,
+        extra_args: &[&str],
+    ) -> Result<T, ExifToolError> {
+        let value = self.json_with_sidecar(file_path, precedence, extra_args)?;
+        serde_path_to_error::deserialize(value).map_err(ExifToolError::from)
+    }
+
+    /// Previews what [`ExifTool::write_tags`] would change, without modifying any file.
+    ///
+    /// Reads the current values of all tags touched by `writes` in a single group-qualified
+    /// `-json` call, applies the assignments to them in order and returns, per file, the tags
+    /// whose value would differ. Tags are compared per group, so `EXIF:Artist` and
+    /// `XMP:Artist` are separate changes. Group deletions like `GPS:all` list each tag of the
+    /// group as removed; tags assigned after the deletion start from an empty value, and
+    /// assignments before it are undone.
+    ///
+    /// The result is a simulation, not a report from `exiftool`: values are compared in
+    /// their printed form, `+=` and `-=` are treated as list operations (not as the numeric
+    /// or date shifts they are for non-list tags), and tags `exiftool` would refuse to write
+    /// are listed too.
+    ///
+    /// # Errors
+    ///
+    /// Files that can't be read get their own error (see [`ExifTool::json_batch_results`]).
+    /// The outer `Result` only fails on communication errors like [`ExifToolError::Io`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError, WriteSet};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let mut et = ExifTool::new()?;
+    /// let writes = WriteSet::new().set("Artist", "Jane Doe").delete("GPS:all");
+    ///
+    /// for (path, changes) in et.write_tags_dry_run([Path::new("a.jpg")], &writes)? {
+    ///     for change in changes? {
+    ///         println!("{}: {} {:?} -> {:?}", path.display(), change.tag, change.old, change.new);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_tags_dry_run<I, P>(
+        &mut self,
+        file_paths: I,
+        writes: &WriteSet,
+    ) -> Result<Vec<FileChanges>, ExifToolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let paths: Vec<PathBuf> = file_paths
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+
+        let mut read_args = writes.read_args();
+        if read_args.is_empty() {
+            // Still read something, so missing files are reported.
+            read_args.push("-FileName".to_string());
+        }
+        let read_args: Vec<&str> = read_args.iter().map(String::as_str).collect();
+        let current = self.json_batch_results(&paths, &read_args)?;
+
+        Ok(current
+            .into_iter()
+            .map(|(path, result)| {
+                let changes = result.map(|value| match value {
+                    Value::Object(map) => writes.diff(&map),
+                    _ => writes.diff(&Map::new()),
+                });
+                (path, changes)
+            })
+            .collect())
+    }
+
+    /// Writes tags to a copy of in-memory file contents and returns the modified file.
+    ///
+    /// Runs `exiftool -TAG=VALUE... {extra_args...} -o - -` in a separate, short-lived process
+    /// configured like this instance (see [`ExifTool::json_from_bytes`]). `data` is piped to
+    /// its stdin and the rewritten file is read from its stdout, so no file is created or
+    /// modified on disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `data`: The original file contents.
+    /// * `tags`: `(tag, value)` pairs to write. An empty value deletes the tag, and tags
+    ///   may be group-qualified (e.g. `"XMP:Title"`) or groups (e.g. `("all", "")`).
+    /// * `extra_args`: Additional arguments for `exiftool`, e.g. `"-m"` to ignore minor errors.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExifToolError::ExifToolProcess`] if `exiftool` reports an error (e.g.
+    ///   `Unknown file type`) or writes no output, e.g. because none of the tags is writable.
+    /// * [`ExifToolError::Timeout`] if the configured timeout elapses.
+    /// * [`ExifToolError::ExifToolNotFound`] or [`ExifToolError::Io`] if running the process
+    ///   fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let exiftool = ExifTool::new()?;
+    /// let original: Vec<u8> = std::fs::read("image.jpg")?;
+    ///
+    /// // Strip GPS tags and set the copyright in one pass.
+    /// let rewritten = exiftool.write_tags_to_bytes(
+    ///     &original,
+    ///     &[("gps:all", ""), ("Copyright", "© Example Corp")],
+    ///     &[],
+    /// )?;
+    /// std::fs::write("served.jpg", rewritten)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_tags_to_bytes<T: ToString>(
+        &self,
+        data: &[u8],
+        tags: &[(&str, T)],
+        extra_args: &[&str],
+    ) -> Result<Vec<u8>, ExifToolError> {
+        let tag_args: Vec<String> = tags
+            .iter()
+            .map(|(tag, value)| format!("-{}={}", tag, value.to_string()))
+            .collect();
+        let mut args: Vec<&str> = tag_args.iter().map(String::as_str).collect();
+        args.extend_from_slice(extra_args);
+        args.extend(["-o", "-", "-"]);
+
+        let output = run_oneshot(&self.builder, &args, data, self.timeout)?;
+        check_stderr(&args, &output.stderr)?;
+        if output.stdout.is_empty() {
+            // e.g. "Nothing to do." when none of the tags could be written.
+            return Err(ExifToolError::ExifToolProcess {
+                message: output
+                    .stderr
+                    .last()
+                    .cloned()
+                    .unwrap_or_else(|| "No output file was written".to_string()),
+                std_err: output.stderr.join("\n"),
+                command_args: args.join(" "),
+            });
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// The marker printed by exiftool after command `command_id`, on stdout for `-execute<N>`
+/// and on stderr through `-echo4`.
+pub(crate) fn ready_marker(command_id: u64) -> String {
+    format!("{{ready{}}}", command_id)
+}
+
+/// Returns the command id if `line` is a `{ready<N>}` marker.
+pub(crate) fn parse_ready_marker(line: &str) -> Option<u64> {
+    line.trim_end()
+        .strip_prefix("{ready")?
+        .strip_suffix('}')?
+        .parse()
+        .ok()
+}
+
+/// The arguments of `exiftool -tagsFromFile {src} {selection...} [backup] {extra_args...} {dst}`.
+fn copy_args<'a>(
+    src: &'a str,
+    dst: &'a str,
+    selection_args: &'a [String],
+    backup_arg: Option<&'a str>,
+    extra_args: &[&'a str],
+) -> Vec<&'a str> {
+    let mut args = vec!["-tagsFromFile", src];
+    args.extend(selection_args.iter().map(String::as_str));
+    args.extend(backup_arg);
+    args.extend_from_slice(extra_args);
+    args.push(dst);
+    args
+}
+
+/// Receives from `receiver`, giving up once `deadline` has passed.
+fn recv_until<T>(receiver: &Receiver<T>, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+    match deadline {
+        Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    }
+}
+
+/// Turns the stderr lines of a finished command into an error, logging any warnings.
+///
+/// Shared between the blocking and async clients so both report errors identically.
+pub(crate) fn check_stderr(args: &[&str], stderr_lines: &[String]) -> Result<(), ExifToolError> {
+    if stderr_lines.is_empty() {
+        return Ok(());
+    }
+    // Combine args for error reporting
+    let command_args = args.join(" ");
+    let combined_stderr = stderr_lines.join("\n");
+
+    // Check for specific common errors first
+    for err_line in stderr_lines {
+        if let Some(filename) = err_line.strip_prefix("Error: File not found - ") {
+            return Err(ExifToolError::FileNotFound {
+                path: PathBuf::from(filename.trim()),
+                command_args,
+            });
+        } else if err_line.contains("Error:") {
+            return Err(ExifToolError::ExifToolProcess {
+                message: err_line.to_string(),
+                std_err: combined_stderr,
+                command_args,
+            });
+        } else if err_line.contains("Warning:") {
+            warn!("ExifTool Warning - {}", err_line);
+        }
+    }
+    Ok(())
+}
+
+/// Parses the output of a `-json` batch command and adds the `Warning` tags of each file to
+/// the stderr warnings. `args` is the full command, including `-json`.
+pub(crate) fn json_batch_warnings(
+    args: &[&str],
+    output: WithWarnings<Vec<u8>>,
+    path_strs: &[String],
+) -> Result<WithWarnings<Vec<Value>>, ExifToolError> {
+    let WithWarnings {
+        value: stdout,
+        mut warnings,
+    } = output;
+    match parse_json_output(&args[1..], &stdout)? {
+        Value::Array(array) => {
+            for warning in array.iter().flat_map(Warning::from_json_object) {
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+            Ok(WithWarnings {
+                value: array,
+                warnings,
+            })
+        }
+        _ => Err(ExifToolError::UnexpectedFormat {
+            path: path_strs.join(", "),
+            command_args: args.join(" "),
+        }),
+    }
+}
+
+/// Splits the output of a `-json` batch command into one result per input path.
+/// `args` is the full command, including `-json`.
+pub(crate) fn split_batch_output(
+    paths: Vec<PathBuf>,
+    args: &[&str],
+    output: CommandOutput,
+) -> Result<Vec<BatchResult>, ExifToolError> {
+    let command_args = args.join(" ");
+
+    // Index JSON objects by `SourceFile`. exiftool reports paths with forward slashes.
+    let mut by_source: HashMap<String, VecDeque<Value>> = HashMap::new();
+    if !output.stdout.is_empty() {
+        let objects: Vec<Value> = serde_json::from_slice(&output.stdout)?;
+        for object in objects {
+            let source = object
+                .get("SourceFile")
+                .and_then(Value::as_str)
+                .map(|s| s.replace('\\', "/"))
+                .unwrap_or_default();
+            by_source.entry(source).or_default().push_back(object);
+        }
+    }
+
+    let error_lines: Vec<&String> = output
+        .stderr
+        .iter()
+        .filter(|line| line.starts_with("Error:"))
+        .collect();
+    for line in output.stderr.iter().filter(|l| l.contains("Warning:")) {
+        warn!("ExifTool Warning - {}", line);
+    }
+    let file_error_line = |path_str: &str| {
+        let suffix = format!(" - {}", path_str);
+        error_lines
+            .iter()
+            .find(|line| line.ends_with(&suffix))
+            .map(|line| line.as_str())
+    };
+    // Errors that can't be attributed to a specific file, e.g. invalid options.
+    let general_error = error_lines
+        .iter()
+        .find(|line| {
+            !paths
+                .iter()
+                .any(|p| line.ends_with(&format!(" - {}", p.to_string_lossy())))
+        })
+        .map(|line| line.to_string());
+
+    let results = paths
+        .into_iter()
+        .map(|path| {
+            let path_str = path.to_string_lossy().into_owned();
+            let object = by_source
+                .get_mut(&path_str.replace('\\', "/"))
+                .and_then(VecDeque::pop_front);
+
+            let result = match (object, file_error_line(&path_str)) {
+                (Some(object), _) => match object.get("Error").and_then(Value::as_str) {
+                    Some(message) => Err(ExifToolError::ExifToolProcess {
+                        message: format!("Error: {}", message),
+                        std_err: output.stderr.join("\n"),
+                        command_args: command_args.clone(),
+                    }),
+                    None => Ok(object),
+                },
+                (None, Some(line)) if line.starts_with("Error: File not found - ") => {
+                    Err(ExifToolError::FileNotFound {
+                        path: path.clone(),
+                        command_args: command_args.clone(),
+                    })
+                }
+                (None, Some(line)) => Err(ExifToolError::ExifToolProcess {
+                    message: line.to_string(),
+                    std_err: output.stderr.join("\n"),
+                    command_args: command_args.clone(),
+                }),
+                (None, None) => match &general_error {
+                    Some(line) => Err(ExifToolError::ExifToolProcess {
+                        message: line.clone(),
+                        std_err: output.stderr.join("\n"),
+                        command_args: command_args.clone(),
+                    }),
+                    None => Err(ExifToolError::UnexpectedFormat {
+                        path: path_str,
+                        command_args: command_args.clone(),
+                    }),
+                },
+            };
+            (path, result)
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Parses the stdout of a `-json` command. `args` are the arguments *excluding* `-json`.
+pub(crate) fn parse_json_output(
+    args: &[&str],
+    output_bytes: &[u8],
+) -> Result<Value, ExifToolError> {
+    // Handle empty output gracefully - ExifTool might return empty output for
+    // certain commands or errors that weren't caught via stderr.
+    if output_bytes.is_empty() {
+        // Or return Ok(Value::Null) or Ok(Value::Array(vec![])) ?
+        return Err(ExifToolError::UnexpectedFormat {
+            path: args
+                .iter()
+                .find(|a| !a.starts_with('-'))
+                .unwrap_or(&"<unknown>")
+                .to_string(),
+            command_args: format!("-json {}", args.join(" ")),
+        });
+    }
+    let value: Value = serde_json::from_slice(output_bytes)?;
+    Ok(value)
+}
+
+impl Drop for ExifTool {
+    /// Attempts to gracefully close the `exiftool` process and then kills it
+    /// if it hasn't terminated after a short grace period (implicit in `kill`).
+    fn drop(&mut self) {
+        // 1. Attempt graceful shutdown by sending exit commands.
+        if let Err(e) = self.close() {
+            // Log if closing failed, but proceed to kill anyway.
+            warn!("Failed to send close command to exiftool process: {}", e);
+        }
+
+        // 2. Kill the process. This ensures cleanup even if graceful shutdown fails
+        //    or hangs. `kill()` on Unix sends SIGKILL; on Windows, TerminateProcess.
+        if let Err(e) = self.child.kill() {
+            // Log if killing failed (e.g., process already dead).
             warn!(
                 "Failed to kill exiftool process (may already be dead): {}",
                 e
@@ -1199,6 +2758,7 @@ mod tests {
     use super::*;
     use crate::g2::ExifData;
     use crate::utils::test_helpers::{list_files_recursive, test_image_path}; // Use updated helper
+    use crate::write::TagChange;
     use assert_matches::assert_matches;
     use image::ImageReader;
     use serde::Deserialize;
@@ -1257,6 +2817,208 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_timeout_restarts_process() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let path = test_image_path();
+        let path_str = path.to_str().unwrap();
+
+        // `-if` evaluates Perl, which lets us make exiftool hang on purpose.
+        let result = et.execute_raw_with_timeout(
+            &["-if", "sleep 10; 1", path_str],
+            Some(Duration::from_millis(500)),
+        );
+        assert_matches!(
+            result,
+            Err(ExifToolError::Timeout { elapsed, .. }) if elapsed < Duration::from_secs(10)
+        );
+
+        // The instance is usable again after the restart.
+        let make: String = et.read_tag(&path, "Make")?;
+        assert_eq!(make, "Huawei");
+
+        // And the instance-wide timeout applies to the helper methods too.
+        et.set_timeout(Some(Duration::from_secs(30)));
+        let make: String = et.read_tag(&path, "Make")?;
+        assert_eq!(make, "Huawei");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ready_marker() {
+        assert_eq!(parse_ready_marker(&ready_marker(42)), Some(42));
+        assert_eq!(parse_ready_marker("{ready7}\r"), Some(7));
+        assert_eq!(parse_ready_marker("{ready}"), None);
+        assert_eq!(parse_ready_marker("Warning: {ready3} in comment"), None);
+    }
+
+    #[test]
+    fn test_ready_marker_in_output_is_ignored() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let path = test_image_path();
+        // Print fake markers to stdout and stderr as part of the command's own output.
+        let lines = et.execute_lines(&[
+            "-echo3",
+            "{ready}",
+            "-echo4",
+            "{ready}",
+            "-S",
+            "-Make",
+            path.to_str().unwrap(),
+        ])?;
+        assert_eq!(lines, vec!["Make: Huawei", "{ready}"]);
+
+        let make: String = et.read_tag(&path, "Make")?;
+        assert_eq!(make, "Huawei");
+        Ok(())
+    }
+
+    #[test]
+    fn test_stderr_attributed_to_command() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let good_path = test_image_path();
+        let bad_path = Path::new("data/non_existent_file.jpg");
+        for _ in 0..20 {
+            let result = et.json(bad_path, &[]);
+            assert_matches!(result, Err(ExifToolError::FileNotFound { path, .. }) if path == bad_path);
+            assert!(et.json(&good_path, &[]).is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_with_stderr() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let path = test_image_path();
+        let output = et.execute_with_stderr(&[
+            "-json",
+            "-Make",
+            path.to_str().unwrap(),
+            "data/non_existent_file.jpg",
+        ])?;
+        assert!(String::from_utf8(output.stdout)?.contains("Huawei"));
+        assert_eq!(
+            output.stderr,
+            vec!["Error: File not found - data/non_existent_file.jpg"]
+        );
+
+        let output = et.execute_with_stderr(&["-json", "-Make", path.to_str().unwrap()])?;
+        assert!(output.stderr.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_from_bytes() -> Result<(), ExifToolError> {
+        let et = ExifTool::new()?;
+        let data = fs::read(test_image_path())?;
+        let value = et.json_from_bytes(&data, &["-Make"])?;
+        assert_eq!(value["SourceFile"], "-");
+        assert_eq!(value["Make"], "Huawei");
+
+        let result = et.json_from_bytes(b"not an image", &[]);
+        assert_matches!(result, Err(ExifToolError::ExifToolProcess { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_metadata_from_reader() -> Result<(), ExifToolError> {
+        #[derive(Debug, Deserialize)]
+        struct Make {
+            #[serde(rename = "Make")]
+            make: String,
+        }
+
+        let et = ExifTool::new()?;
+        let file = fs::File::open(test_image_path())?;
+        let metadata: Make = et.read_metadata_from_reader(file, &["-Make"])?;
+        assert_eq!(metadata.make, "Huawei");
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_from_stalled_reader_times_out() -> Result<(), ExifToolError> {
+        // Never reaches EOF, and each read takes longer than the timeout.
+        struct Stalled;
+        impl Read for Stalled {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                thread::sleep(Duration::from_millis(500));
+                buf[0] = 0xFF;
+                Ok(1)
+            }
+        }
+
+        let et = ExifTool::builder()
+            .timeout(Duration::from_millis(200))
+            .build()?;
+        let start = Instant::now();
+        let result = et.json_from_reader(Stalled, &[]);
+        assert_matches!(result, Err(ExifToolError::Timeout { .. }));
+        // The writer thread stops at its first write after the process was killed.
+        assert!(start.elapsed() < Duration::from_secs(3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_tags_to_bytes() -> Result<(), ExifToolError> {
+        let et = ExifTool::new()?;
+        let path = test_image_path();
+        let original = fs::read(&path)?;
+
+        let rewritten = et.write_tags_to_bytes(&original, &[("Comment", "in memory")], &[])?;
+        assert_ne!(rewritten, original);
+        let value = et.json_from_bytes(&rewritten, &["-Comment"])?;
+        assert_eq!(value["Comment"], "in memory");
+
+        // The source file is untouched.
+        assert_eq!(fs::read(&path)?, original);
+
+        let result = et.write_tags_to_bytes(b"not an image", &[("Comment", "x")], &[]);
+        assert_matches!(result, Err(ExifToolError::ExifToolProcess { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_with_diagnostics() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let path = test_image_path();
+        // An undefined tag is skipped with a warning, so nothing gets written.
+        let result = et.execute_with_diagnostics(&["-NonExistentTag=1", path.to_str().unwrap()])?;
+        assert_eq!(
+            result.warnings,
+            vec![Warning {
+                message: "Tag 'NonExistentTag' is not defined".to_string(),
+                file: None,
+                minor: false,
+            }]
+        );
+
+        let result = et.json_batch_with_diagnostics([&path], &["-Make"])?;
+        assert_eq!(result.value.len(), 1);
+        assert!(result.warnings.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stderr_never_leaks_between_commands() -> Result<(), ExifToolError> {
+        // Regression test: stderr used to be collected within a short polling window, so under
+        // load errors of one command could surface on the next one (or get lost).
+        let mut et = ExifTool::new()?;
+        let good_path = test_image_path();
+        for i in 0..2000 {
+            let results = et.json_batch([&good_path], &["-FileName"])?;
+            assert_eq!(results.len(), 1, "iteration {}", i);
+
+            let bad_path = PathBuf::from(format!("data/non_existent_{}.jpg", i));
+            let result = et.json_batch([&bad_path], &["-FileName"]);
+            assert_matches!(
+                result,
+                Err(ExifToolError::FileNotFound { path, .. }) if path == bad_path,
+                "iteration {}", i
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_read_metadata_json_single() -> Result<(), ExifToolError> {
         let mut et = ExifTool::new()?;
@@ -1295,6 +3057,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_json_batch_results_partial_failure() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let good1 = test_image_path();
+        let missing = PathBuf::from("data/non_existent_file.jpg");
+        let good2 = PathBuf::from("data/valid/other_images/jpg/gps/DSCN0010.jpg");
+        let paths = [good1.clone(), missing.clone(), good2.clone(), good1.clone()];
+
+        let results = et.json_batch_results(&paths, &["-FileName"])?;
+
+        assert_eq!(results.len(), 4);
+        let result_paths: Vec<_> = results.iter().map(|(p, _)| p.clone()).collect();
+        assert_eq!(result_paths, paths);
+
+        for (path, result) in [&results[0], &results[2], &results[3]] {
+            let value = result.as_ref().expect("existing file should succeed");
+            assert_eq!(
+                value.get("FileName").and_then(Value::as_str),
+                path.file_name().and_then(|n| n.to_str())
+            );
+        }
+        assert_matches!(
+            &results[1].1,
+            Err(ExifToolError::FileNotFound { path, .. }) if *path == missing
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_batch_results_empty() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let results = et.json_batch_results(Vec::<PathBuf>::new(), &[])?;
+        assert!(results.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_batch_output_json_error_tag() -> Result<(), ExifToolError> {
+        let output = CommandOutput {
+            stdout: br#"[{"SourceFile": "data/notes.txt", "Error": "Unknown file type"}]"#.to_vec(),
+            stderr: vec![],
+        };
+        let results =
+            split_batch_output(vec![PathBuf::from("data/notes.txt")], &["-json"], output)?;
+        assert_matches!(
+            &results[0].1,
+            Err(ExifToolError::ExifToolProcess { message, .. }) if message == "Error: Unknown file type"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_read_tag_json() -> Result<(), ExifToolError> {
         let mut et = ExifTool::new()?;
@@ -1316,6 +3129,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_tag_values() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let path = test_image_path();
+        let make = et.read_tag_value(path.as_path(), "Make")?;
+        assert_eq!(make.raw, json!("Huawei"));
+        assert_eq!(make.to_string(), "Huawei");
+
+        let missing = et.read_tag_value(path.as_path(), "NonExistentTag123");
+        assert_matches!(missing, Err(ExifToolError::TagNotFound { .. }));
+
+        let raw: Option<String> = et.read_tag_as(path.as_path(), "Make", Representation::Raw)?;
+        assert_eq!(raw.as_deref(), Some("Huawei"));
+        Ok(())
+    }
+
     #[test]
     fn test_read_tag_generic() -> Result<(), ExifToolError> {
         let mut et = ExifTool::new()?;
@@ -1374,6 +3203,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_command_reports_field_path() -> Result<(), ExifToolError> {
+        #[derive(Deserialize, Debug)]
+        #[serde(rename_all = "PascalCase")]
+        #[allow(dead_code)]
+        struct Wrong {
+            make: u32,
+        }
+
+        let mut et = ExifTool::new()?;
+        let command = Command::new().file(test_image_path()).tag("Make");
+        let result: Result<Vec<Wrong>, _> = et.read_command(&command);
+        assert_matches!(result, Err(ExifToolError::Deserialization { path, .. }) if path == "Make");
+        Ok(())
+    }
+
     #[test]
     fn test_read_tag_binary() -> Result<(), ExifToolError> {
         let mut et = ExifTool::new()?;
@@ -1428,7 +3273,8 @@ mod tests {
 
         // Write string
         let new_author = "Rust Writer Test";
-        et.write_tag(&temp_img, "Author", new_author, &[])?;
+        let report = et.write_tag(&temp_img, "Author", new_author, &[])?;
+        assert_eq!(report.updated, 1);
 
         let read_author: String = et.read_tag(&temp_img, "Author")?;
         assert_eq!(read_author, new_author);
@@ -1447,6 +3293,287 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_tags_set() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let temp_img = setup_temp_image()?;
+
+        let writes = WriteSet::new()
+            .set("XMP:Title", "Sunset")
+            .set("Artist", "Jane Doe")
+            .set("Keywords", "beach")
+            .append("Keywords", "sea")
+            .copy("XMP:Description", "Make");
+        let report = et.write_tags([&temp_img], &writes, &["-overwrite_original"])?;
+
+        let title: String = et.read_tag(&temp_img, "Title")?;
+        let artist: String = et.read_tag(&temp_img, "Artist")?;
+        let keywords: Vec<String> = et.read_tag(&temp_img, "Keywords")?;
+        let description: String = et.read_tag(&temp_img, "Description")?;
+        fs::remove_file(&temp_img)?;
+
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.unchanged, 0);
+        assert_eq!(title, "Sunset");
+        assert_eq!(artist, "Jane Doe");
+        assert_eq!(keywords, ["beach", "sea"]);
+        assert_eq!(description, "Huawei");
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_tags_partial_failure() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let temp_img = setup_temp_image()?;
+        let missing = PathBuf::from("data/non_existent_file.jpg");
+
+        let writes = WriteSet::new().set("Artist", "Jane Doe");
+        let result = et.write_tags([&temp_img, &missing], &writes, &["-overwrite_original"]);
+        fs::remove_file(&temp_img)?;
+
+        let report = result?;
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.failed, 1);
+
+        // When nothing could be written, the error itself is returned.
+        let result = et.write_tags([&missing], &writes, &[]);
+        assert_matches!(result, Err(ExifToolError::FileNotFound { path, .. }) if path == missing);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_tag_nothing_written() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let result = et.write_tag(&test_image_path(), "NonExistentTag", "x", &[]);
+        assert_matches!(result, Err(ExifToolError::NoFilesUpdated { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_tags_empty_set() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let report = et.write_tags([test_image_path()], &WriteSet::new(), &[])?;
+        assert_eq!(report, WriteReport::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_backup_policy_restore_and_delete_original() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let temp_img = setup_temp_image()?;
+        let backup = PathBuf::from(format!("{}_original", temp_img.display()));
+        let original = fs::read(&temp_img)?;
+
+        et.write_tag(&temp_img, "Artist", "Jane Doe", &[])?;
+        assert!(backup.exists());
+        assert_eq!(et.restore_original([&temp_img])?, 1);
+        assert!(!backup.exists());
+        assert_eq!(fs::read(&temp_img)?, original);
+
+        et.write_tag(&temp_img, "Artist", "Jane Doe", &[])?;
+        assert_eq!(et.delete_original([&temp_img])?, 1);
+        assert!(!backup.exists());
+        // Nothing left to delete.
+        assert_eq!(et.delete_original([&temp_img])?, 0);
+
+        et.set_backup_policy(BackupPolicy::OverwriteOriginal);
+        et.write_tags([&temp_img], &WriteSet::new().set("Artist", "John Doe"), &[])?;
+        let backup_created = backup.exists();
+        fs::remove_file(&temp_img)?;
+        assert!(!backup_created);
+
+        let missing = PathBuf::from("data/non_existent_file.jpg");
+        assert_matches!(
+            et.restore_original([&missing]),
+            Err(ExifToolError::FileNotFound { .. })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_tags() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        et.set_backup_policy(BackupPolicy::OverwriteOriginal);
+        let temp_img = setup_temp_image()?;
+
+        let selection = CopySelection::all()
+            .redirect("XMP:Make", "Make")
+            .exclude("MakerNotes:all");
+        let result = et.copy_tags(&test_image_path(), &temp_img, &selection, &[]);
+        let make: Result<String, _> = et.read_tag(&temp_img, "XMP:Make");
+        fs::remove_file(&temp_img)?;
+
+        assert_eq!(result?.updated, 1);
+        assert_eq!(make?, "Huawei");
+
+        let missing = PathBuf::from("data/non_existent_file.jpg");
+        let result = et.copy_tags(&test_image_path(), &missing, &CopySelection::all(), &[]);
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sidecar() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        et.set_backup_policy(BackupPolicy::OverwriteOriginal);
+        let temp_img = setup_temp_image()?;
+        let sidecar = temp_img.with_extension("xmp");
+
+        let result = et.sync_from_sidecar(&temp_img, &[]);
+        assert_matches!(
+            result,
+            Err(ExifToolError::FileNotFound { path, command_args })
+                if path == sidecar
+                    && command_args.starts_with(&format!("-tagsFromFile {} -XMP:all", sidecar.display()))
+                    && command_args.ends_with(&temp_img.to_string_lossy().into_owned())
+        );
+
+        let created = et.create_sidecar(&temp_img, &[]);
+        let sidecar_exists = sidecar.exists();
+        let exists_again = et.create_sidecar(&temp_img, &[]);
+        let to_sidecar = et.sync_to_sidecar(&temp_img, &[]);
+        let from_sidecar = et.sync_from_sidecar(&temp_img, &[]);
+        let merged = et.json_with_sidecar(&temp_img, SidecarPrecedence::Embedded, &["-Make"]);
+        fs::remove_file(&temp_img)?;
+        let _ = fs::remove_file(&sidecar);
+
+        assert_eq!(created?.created, 1);
+        assert!(sidecar_exists);
+        assert!(exists_again.is_err());
+        assert_eq!(to_sidecar?.updated, 1);
+        assert_eq!(from_sidecar?.updated, 1);
+        let merged = merged?;
+        assert_eq!(merged["Make"], "Huawei");
+        assert_eq!(merged["SourceFile"], temp_img.to_string_lossy().as_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sidecar_keeps_no_backup_under_default_policy() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        et.set_sidecar_naming(SidecarNaming::AppendExtension);
+        assert_eq!(et.backup_policy(), BackupPolicy::KeepOriginal);
+        let temp_img = setup_temp_image()?;
+        let sidecar = PathBuf::from(format!("{}.xmp", temp_img.display()));
+        let sidecar_backup = PathBuf::from(format!("{}_original", sidecar.display()));
+        let img_backup = PathBuf::from(format!("{}_original", temp_img.display()));
+
+        let created = et.create_sidecar(&temp_img, &[]);
+        let sidecar_exists = sidecar.exists();
+        let to_sidecar = et.sync_to_sidecar(&temp_img, &[]);
+        let sidecar_backed_up = sidecar_backup.exists();
+        let from_sidecar = et.sync_from_sidecar(&temp_img, &[]);
+        let img_backed_up = img_backup.exists();
+        for path in [&temp_img, &sidecar, &sidecar_backup, &img_backup] {
+            let _ = fs::remove_file(path);
+        }
+
+        assert_eq!(created?.created, 1);
+        assert!(sidecar_exists);
+        assert_eq!(to_sidecar?.updated, 1);
+        assert!(!sidecar_backed_up);
+        assert_eq!(from_sidecar?.updated, 1);
+        assert!(img_backed_up);
+        Ok(())
+    }
+
+    /// A minimal ICC profile: a 128-byte header and an empty tag table.
+    fn minimal_icc_profile() -> Vec<u8> {
+        let mut icc = vec![0u8; 132];
+        icc[0..4].copy_from_slice(&132u32.to_be_bytes());
+        icc[8..12].copy_from_slice(&[0x02, 0x10, 0x00, 0x00]);
+        icc[12..16].copy_from_slice(b"mntr");
+        icc[16..20].copy_from_slice(b"RGB ");
+        icc[20..24].copy_from_slice(b"XYZ ");
+        icc[36..40].copy_from_slice(b"acsp");
+        icc
+    }
+
+    #[test]
+    fn test_strip_metadata() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        et.set_backup_policy(BackupPolicy::OverwriteOriginal);
+        let first = setup_temp_image()?;
+        let second = setup_temp_image()?;
+        let icc = minimal_icc_profile();
+        let private_args = [
+            "-G",
+            "-GPS:all",
+            "-*SerialNumber",
+            "-*OwnerName",
+            "-MakerNotes:all",
+            "-Make",
+        ];
+
+        let identifying = WriteSet::new()
+            .set("SerialNumber", "1234567")
+            .set("OwnerName", "Jane Doe");
+        let seeded = et.write_tags([&first, &second], &identifying, &[]);
+        let icc_written = et.write_tag_binary(&second, "ICC_Profile", &icc, &[]);
+        let before = et.json(&first, &private_args);
+
+        let single = et.strip_metadata(&first, &StripPolicy::PrivacyOnly, &[]);
+        let after_privacy = et.json(&first, &private_args);
+
+        let batch = et.strip_metadata_batch([&first, &second], &StripPolicy::KeepColorProfile, &[]);
+        let make: Result<Option<String>, _> = et.read_tag(&second, "Make");
+        let kept_icc = et.read_tag_binary(&second, "ICC_Profile");
+        fs::remove_file(&first)?;
+        fs::remove_file(&second)?;
+
+        assert_eq!(seeded?.updated, 2);
+        assert_eq!(icc_written?.updated, 1);
+        let is_private = |tag: &str| {
+            tag.starts_with("GPS:")
+                || tag.starts_with("MakerNotes:")
+                || tag.ends_with("SerialNumber")
+                || tag.ends_with("OwnerName")
+        };
+        let before = before?;
+        let before = before.as_object().unwrap();
+        assert!(before.keys().any(|tag| tag.starts_with("GPS:")));
+        assert_eq!(before["EXIF:SerialNumber"], "1234567");
+        assert_eq!(before["EXIF:OwnerName"], "Jane Doe");
+
+        assert_eq!(single?.updated, 1);
+        let after_privacy = after_privacy?;
+        let after_privacy = after_privacy.as_object().unwrap();
+        assert!(!after_privacy.keys().any(|tag| is_private(tag)));
+        assert_eq!(after_privacy["EXIF:Make"], "Huawei");
+
+        assert_eq!(batch?.updated, 2);
+        assert_eq!(make?, None);
+        assert_eq!(kept_icc?, icc);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_tags_dry_run() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let image = test_image_path();
+        let missing = PathBuf::from("data/non_existent_file.jpg");
+        let original = fs::read(&image)?;
+
+        let writes = WriteSet::new()
+            .set("Make", "Huawei")
+            .copy("XMP:Title", "Make");
+        let results = et.write_tags_dry_run([&image, &missing], &writes)?;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, image);
+        assert_eq!(
+            results[0].1.as_ref().unwrap(),
+            &vec![TagChange {
+                tag: "XMP:Title".to_string(),
+                old: None,
+                new: Some(json!("Huawei")),
+            }]
+        );
+        assert_matches!(&results[1].1, Err(ExifToolError::FileNotFound { path, .. }) if *path == missing);
+        assert_eq!(fs::read(&image)?, original);
+        Ok(())
+    }
+
     #[test]
     fn test_write_tag_binary() -> Result<(), ExifToolError> {
         let mut et = ExifTool::new()?;
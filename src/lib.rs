@@ -81,11 +81,34 @@
 //! ```
 
 // Public API
+#[cfg(feature = "tokio")]
+mod async_exiftool;
+mod builder;
+mod command;
 mod error;
 mod exiftool;
+mod metadata_iter;
+mod oneshot;
+mod pool;
+mod sidecar;
+mod tag_value;
+mod warning;
+mod write;
 
+#[cfg(feature = "tokio")]
+pub use async_exiftool::AsyncExifTool;
+pub use builder::ExifToolBuilder;
+pub use command::{Command, GroupLevel, SortOrder};
 pub use error::ExifToolError;
-pub use exiftool::ExifTool;
+pub use exiftool::{BatchResult, CommandOutput, ExifTool};
+pub use metadata_iter::{MetadataIter, DEFAULT_CHUNK_SIZE};
+pub use pool::{ExifToolPool, PooledExifTool};
+pub use sidecar::{SidecarNaming, SidecarPrecedence};
+pub use tag_value::{Representation, TagValue};
+pub use warning::{Warning, WithWarnings};
+pub use write::{
+    BackupPolicy, CopySelection, FileChanges, StripPolicy, TagChange, WriteReport, WriteSet,
+};
 
 pub mod parse_fn;
 mod structs;
@@ -0,0 +1,193 @@
+use crate::error::ExifToolError;
+use crate::exiftool::{BatchResult, ExifTool};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// Number of files sent to `exiftool` per command by [`MetadataIter`], unless changed with
+/// [`MetadataIter::chunk_size`].
+pub const DEFAULT_CHUNK_SIZE: usize = 256;
+
+/// A streaming iterator over the metadata of many files, created by
+/// [`ExifTool::read_metadata_iter`].
+///
+/// Input paths are consumed lazily and sent to `exiftool` in chunks. Each chunk's output is
+/// read and parsed as a whole, so memory is bounded per chunk only: at most one chunk of
+/// output is held at a time, and a smaller [`MetadataIter::chunk_size`] lowers the peak.
+///
+/// Every input path yields exactly one `Result`, in input order, so results can be zipped
+/// with the paths. A missing or unreadable file doesn't stop the iteration (see
+/// [`ExifTool::json_batch_results`] for the per-file errors). If a whole chunk fails (e.g.
+/// with [`ExifToolError::Timeout`]), each of its paths yields that error and iteration
+/// continues with the next chunk.
+pub struct MetadataIter<'a, T, I> {
+    exiftool: &'a mut ExifTool,
+    file_paths: I,
+    extra_args: Vec<String>,
+    chunk_size: usize,
+    pending: VecDeque<BatchResult>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, I, P> MetadataIter<'a, T, I>
+where
+    I: Iterator<Item = P>,
+    P: AsRef<Path>,
+{
+    pub(crate) fn new(exiftool: &'a mut ExifTool, file_paths: I, extra_args: &[&str]) -> Self {
+        Self {
+            exiftool,
+            file_paths,
+            extra_args: extra_args.iter().map(|a| a.to_string()).collect(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            pending: VecDeque::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets how many files are sent to `exiftool` per command.
+    ///
+    /// Larger chunks have less per-command overhead, smaller chunks use less memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be at least 1");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Runs `exiftool` on the next chunk of paths and queues one result per path. Returns
+    /// `None` when all paths are consumed.
+    fn next_chunk(&mut self) -> Option<()> {
+        let chunk: Vec<PathBuf> = self
+            .file_paths
+            .by_ref()
+            .take(self.chunk_size)
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        if chunk.is_empty() {
+            return None;
+        }
+        let extra_args: Vec<&str> = self.extra_args.iter().map(String::as_str).collect();
+        match self.exiftool.json_batch_results(&chunk, &extra_args) {
+            Ok(results) => self.pending.extend(results),
+            Err(e) => self
+                .pending
+                .extend(chunk.into_iter().map(|path| (path, Err(e.duplicate())))),
+        }
+        Some(())
+    }
+}
+
+impl<T, I, P> Iterator for MetadataIter<'_, T, I>
+where
+    T: DeserializeOwned,
+    I: Iterator<Item = P>,
+    P: AsRef<Path>,
+{
+    type Item = Result<T, ExifToolError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((_, result)) = self.pending.pop_front() {
+                return Some(result.and_then(|value| {
+                    serde_path_to_error::deserialize(value).map_err(ExifToolError::from)
+                }));
+            }
+            self.next_chunk()?;
+        }
+    }
+}
+
+impl<T, I> std::fmt::Debug for MetadataIter<'_, T, I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetadataIter")
+            .field("extra_args", &self.extra_args)
+            .field("chunk_size", &self.chunk_size)
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_helpers::{list_files_recursive, test_image_path};
+    use assert_matches::assert_matches;
+    use serde::Deserialize;
+    use serde_json::Value;
+    use std::time::Duration;
+
+    #[derive(Debug, Deserialize)]
+    struct FileName {
+        #[serde(rename = "FileName")]
+        file_name: String,
+    }
+
+    #[test]
+    fn test_iter_matches_input_order() -> Result<(), ExifToolError> {
+        let files = list_files_recursive(Path::new("data/valid")).expect("Failed to list files");
+        let mut et = ExifTool::new()?;
+
+        let names = et
+            .read_metadata_iter::<FileName, _, _>(&files, &["-FileName"])
+            .chunk_size(3)
+            .map(|r| r.map(|f| f.file_name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let expected: Vec<String> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_continues_after_missing_file() -> Result<(), ExifToolError> {
+        let missing = PathBuf::from("data/non_existent_file.jpg");
+        let paths = [test_image_path(), missing.clone(), test_image_path()];
+        let mut et = ExifTool::new()?;
+
+        let results: Vec<_> = et
+            .read_metadata_iter::<Value, _, _>(paths, &["-Make"])
+            .chunk_size(2)
+            .collect();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert_matches!(&results[1], Err(ExifToolError::FileNotFound { path, .. }) if *path == missing);
+        assert_eq!(results[2].as_ref().unwrap()["Make"], "Huawei");
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_failed_chunk_yields_error_per_file() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::builder()
+            .timeout(Duration::from_millis(300))
+            .build()?;
+        let paths = [test_image_path(), test_image_path(), test_image_path()];
+
+        let results: Vec<_> = et
+            .read_metadata_iter::<Value, _, _>(paths, &["-if", "sleep 1; 1"])
+            .chunk_size(2)
+            .collect();
+
+        assert_eq!(results.len(), 3);
+        for result in &results {
+            assert_matches!(result, Err(ExifToolError::Timeout { .. }));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_empty() -> Result<(), ExifToolError> {
+        let mut et = ExifTool::new()?;
+        let mut iter = et.read_metadata_iter::<Value, _, _>(Vec::<PathBuf>::new(), &[]);
+        assert!(iter.next().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use crate::builder::ExifToolBuilder;
+use crate::error::ExifToolError;
+use crate::exiftool::CommandOutput;
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::{Child, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How often to check whether a one-shot process with a timeout has exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs a single `exiftool` command in a separate, short-lived process, streaming `input` to
+/// its stdin. Used for commands that read the file from stdin (the `-` file argument), which
+/// isn't possible in stay-open mode, where stdin carries the commands.
+///
+/// The process is configured like the stay-open process described by `builder`. When
+/// `timeout` elapses before the process exits, it is killed and [`ExifToolError::Timeout`] is
+/// returned. The input is written on a separate thread, so the timeout also applies while
+/// `exiftool` isn't reading or `input` is slow. Killing the process ends that thread at its
+/// next write; a `read` call on `input` that never returns can't be interrupted, though.
+pub(crate) fn run_oneshot<R: Read + Send>(
+    builder: &ExifToolBuilder,
+    args: &[&str],
+    mut input: R,
+    timeout: Option<Duration>,
+) -> Result<CommandOutput, ExifToolError> {
+    let start_time = Instant::now();
+    let mut child = builder
+        .oneshot_command(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(ExifToolError::ExifToolNotFound)?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| io::Error::other("Failed to capture stdin"))?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| io::Error::other("Failed to capture stdout"))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| io::Error::other("Failed to capture stderr"))?;
+
+    // Drain stdout and stderr in the background, so a full pipe can't block the process
+    // while we are still writing its input.
+    let stdout_reader = thread::spawn(move || {
+        let mut buffer = Vec::new();
+        stdout.read_to_end(&mut buffer).map(|_| buffer)
+    });
+    let stderr_reader = thread::spawn(move || {
+        BufReader::new(stderr)
+            .lines()
+            .collect::<Result<Vec<String>, _>>()
+    });
+
+    let (written, waited) = thread::scope(|scope| {
+        let writer = scope.spawn(move || {
+            // exiftool stops reading once it has seen all metadata, so a closed pipe is
+            // expected. Dropping stdin at the end signals EOF.
+            match io::copy(&mut input, &mut stdin) {
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+                _ => Ok(()),
+            }
+        });
+        // If reading the input fails, the writer drops stdin and exiftool exits on its own.
+        let waited = wait_with_deadline(&mut child, timeout.map(|t| start_time + t));
+        let written = writer
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("stdin writer thread panicked")));
+        (written, waited)
+    });
+
+    match waited? {
+        WaitResult::Exited => written?,
+        WaitResult::TimedOut => {
+            return Err(ExifToolError::Timeout {
+                command_args: args.join(" "),
+                elapsed: start_time.elapsed(),
+            })
+        }
+    }
+
+    let stdout = stdout_reader
+        .join()
+        .map_err(|_| io::Error::other("stdout reader thread panicked"))??;
+    let stderr = stderr_reader
+        .join()
+        .map_err(|_| io::Error::other("stderr reader thread panicked"))??;
+    Ok(CommandOutput { stdout, stderr })
+}
+
+enum WaitResult {
+    Exited,
+    TimedOut,
+}
+
+/// Waits for `child` to exit, killing it once `deadline` passes.
+fn wait_with_deadline(child: &mut Child, deadline: Option<Instant>) -> io::Result<WaitResult> {
+    let Some(deadline) = deadline else {
+        child.wait()?;
+        return Ok(WaitResult::Exited);
+    };
+    loop {
+        if child.try_wait()?.is_some() {
+            return Ok(WaitResult::Exited);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(WaitResult::TimedOut);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,381 @@
+//! Enums for the standard EXIF enumerations, like `Orientation` and `Flash`.
+//!
+//! Each type deserializes from the number returned with `-n` as well as from `exiftool`'s
+//! printed string (`"Rotate 90 CW"`). Values outside the standard become `Unknown(n)`, and
+//! unrecognized strings (e.g. from maker notes) `Other(s)`.
+//!
+//! [`Display`](std::fmt::Display) writes the printed string, which can be passed to
+//! [`ExifTool::write_tag`](crate::ExifTool::write_tag), and [`Serialize`] writes the number.
+
+use serde::{
+    de::{self, Deserializer},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+use serde_json::Value;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// Parses `6` or exiftool's `Unknown (6)` for values without a printed name.
+fn numeric_value(s: &str) -> Option<u32> {
+    s.parse().ok().or_else(|| {
+        s.strip_prefix("Unknown (")?
+            .strip_suffix(')')?
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
+macro_rules! exif_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($variant:ident = $value:literal => $printed:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $printed, "` (", $value, ")")]
+                $variant,
+            )*
+            /// A number not defined by the standard, printed as `Unknown (n)`.
+            Unknown(u32),
+            /// A printed value that isn't recognized, e.g. from maker notes.
+            Other(String),
+        }
+
+        impl $name {
+            /// The variant for a numeric (`-n`) value.
+            pub fn from_value(value: u32) -> Self {
+                match value {
+                    $($value => Self::$variant,)*
+                    other => Self::Unknown(other),
+                }
+            }
+
+            /// The numeric value, or `None` for [`Self::Other`].
+            pub fn value(&self) -> Option<u32> {
+                match self {
+                    $(Self::$variant => Some($value),)*
+                    Self::Unknown(value) => Some(*value),
+                    Self::Other(_) => None,
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            /// Parses a printed value (ignoring case) or a number.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                $(
+                    if s.eq_ignore_ascii_case($printed) {
+                        return Ok(Self::$variant);
+                    }
+                )*
+                Ok(match numeric_value(s) {
+                    Some(value) => Self::from_value(value),
+                    None => Self::Other(s.to_string()),
+                })
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(Self::$variant => f.write_str($printed),)*
+                    Self::Unknown(value) => write!(f, "Unknown ({})", value),
+                    Self::Other(s) => f.write_str(s),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match (self.value(), self) {
+                    (Some(value), _) => serializer.serialize_u32(value),
+                    (None, other) => serializer.serialize_str(&other.to_string()),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                match Value::deserialize(deserializer)? {
+                    Value::Number(n) => n
+                        .as_u64()
+                        .and_then(|n| u32::try_from(n).ok())
+                        .map(Self::from_value)
+                        .ok_or_else(|| {
+                            de::Error::custom(format!(
+                                concat!("invalid ", stringify!($name), ": {}"),
+                                n
+                            ))
+                        }),
+                    Value::String(s) => Ok(s.parse().unwrap_or_else(|e| match e {})),
+                    other => Err(de::Error::custom(format!(
+                        concat!("unexpected type for ", stringify!($name), ": {:?}"),
+                        other
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+exif_enum! {
+    /// How the image should be rotated or flipped for display (`Orientation`).
+    Orientation {
+        Horizontal = 1 => "Horizontal (normal)",
+        MirrorHorizontal = 2 => "Mirror horizontal",
+        Rotate180 = 3 => "Rotate 180",
+        MirrorVertical = 4 => "Mirror vertical",
+        MirrorHorizontalRotate270 = 5 => "Mirror horizontal and rotate 270 CW",
+        Rotate90 = 6 => "Rotate 90 CW",
+        MirrorHorizontalRotate90 = 7 => "Mirror horizontal and rotate 90 CW",
+        Rotate270 = 8 => "Rotate 270 CW",
+    }
+}
+
+impl Orientation {
+    /// The orientation for a video's `Rotation` in degrees clockwise, e.g. `90` or `-90`.
+    /// `None` if it isn't a multiple of 90.
+    pub fn from_rotation(degrees: i32) -> Option<Self> {
+        match degrees.rem_euclid(360) {
+            0 => Some(Orientation::Horizontal),
+            90 => Some(Orientation::Rotate90),
+            180 => Some(Orientation::Rotate180),
+            270 => Some(Orientation::Rotate270),
+            _ => None,
+        }
+    }
+
+    /// Whether this is one of the eight values defined by EXIF, i.e. not [`Orientation::Unknown`]
+    /// or [`Orientation::Other`]. The transform methods treat other values as
+    /// [`Orientation::Horizontal`].
+    pub fn is_standard(&self) -> bool {
+        matches!(self.value(), Some(1..=8))
+    }
+
+    /// The clockwise rotation to apply for display, after mirroring horizontally if
+    /// [`Orientation::is_mirrored`]: 0, 90, 180 or 270.
+    pub fn rotation_degrees(&self) -> u16 {
+        match self {
+            Orientation::Rotate90 | Orientation::MirrorHorizontalRotate90 => 90,
+            Orientation::Rotate180 | Orientation::MirrorVertical => 180,
+            Orientation::Rotate270 | Orientation::MirrorHorizontalRotate270 => 270,
+            _ => 0,
+        }
+    }
+
+    /// Whether the image has to be mirrored horizontally for display.
+    pub fn is_mirrored(&self) -> bool {
+        matches!(
+            self,
+            Orientation::MirrorHorizontal
+                | Orientation::MirrorVertical
+                | Orientation::MirrorHorizontalRotate90
+                | Orientation::MirrorHorizontalRotate270
+        )
+    }
+
+    /// Whether width and height swap for display, i.e. the image is rotated by 90 or 270 degrees.
+    pub fn swaps_dimensions(&self) -> bool {
+        matches!(self.rotation_degrees(), 90 | 270)
+    }
+
+    /// The displayed `(width, height)` of an image stored as `width` x `height`.
+    pub fn display_dimensions(&self, width: u32, height: u32) -> (u32, u32) {
+        if self.swaps_dimensions() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+}
+
+exif_enum! {
+    /// The flash mode and whether it fired (`Flash`).
+    Flash {
+        NoFlash = 0x00 => "No Flash",
+        Fired = 0x01 => "Fired",
+        FiredReturnNotDetected = 0x05 => "Fired, Return not detected",
+        FiredReturnDetected = 0x07 => "Fired, Return detected",
+        OnDidNotFire = 0x08 => "On, Did not fire",
+        OnFired = 0x09 => "On, Fired",
+        OnReturnNotDetected = 0x0d => "On, Return not detected",
+        OnReturnDetected = 0x0f => "On, Return detected",
+        OffDidNotFire = 0x10 => "Off, Did not fire",
+        OffDidNotFireReturnNotDetected = 0x14 => "Off, Did not fire, Return not detected",
+        AutoDidNotFire = 0x18 => "Auto, Did not fire",
+        AutoFired = 0x19 => "Auto, Fired",
+        AutoFiredReturnNotDetected = 0x1d => "Auto, Fired, Return not detected",
+        AutoFiredReturnDetected = 0x1f => "Auto, Fired, Return detected",
+        NoFlashFunction = 0x20 => "No flash function",
+        OffNoFlashFunction = 0x30 => "Off, No flash function",
+        FiredRedEye = 0x41 => "Fired, Red-eye reduction",
+        FiredRedEyeReturnNotDetected = 0x45 => "Fired, Red-eye reduction, Return not detected",
+        FiredRedEyeReturnDetected = 0x47 => "Fired, Red-eye reduction, Return detected",
+        OnRedEye = 0x49 => "On, Red-eye reduction",
+        OnRedEyeReturnNotDetected = 0x4d => "On, Red-eye reduction, Return not detected",
+        OnRedEyeReturnDetected = 0x4f => "On, Red-eye reduction, Return detected",
+        OffRedEye = 0x50 => "Off, Red-eye reduction",
+        AutoDidNotFireRedEye = 0x58 => "Auto, Did not fire, Red-eye reduction",
+        AutoFiredRedEye = 0x59 => "Auto, Fired, Red-eye reduction",
+        AutoFiredRedEyeReturnNotDetected = 0x5d => "Auto, Fired, Red-eye reduction, Return not detected",
+        AutoFiredRedEyeReturnDetected = 0x5f => "Auto, Fired, Red-eye reduction, Return detected",
+    }
+}
+
+impl Flash {
+    /// Whether the flash fired, from bit 0 of the value. `None` for [`Flash::Other`].
+    pub fn fired(&self) -> Option<bool> {
+        self.value().map(|value| value & 1 == 1)
+    }
+}
+
+exif_enum! {
+    /// The metering mode used for the exposure (`MeteringMode`).
+    MeteringMode {
+        Unspecified = 0 => "Unknown",
+        Average = 1 => "Average",
+        CenterWeightedAverage = 2 => "Center-weighted average",
+        Spot = 3 => "Spot",
+        MultiSpot = 4 => "Multi-spot",
+        MultiSegment = 5 => "Multi-segment",
+        Partial = 6 => "Partial",
+        OtherMode = 255 => "Other",
+    }
+}
+
+exif_enum! {
+    /// The exposure program selected on the camera (`ExposureProgram`).
+    ExposureProgram {
+        NotDefined = 0 => "Not Defined",
+        Manual = 1 => "Manual",
+        ProgramAe = 2 => "Program AE",
+        AperturePriority = 3 => "Aperture-priority AE",
+        ShutterPriority = 4 => "Shutter speed priority AE",
+        Creative = 5 => "Creative (Slow speed)",
+        Action = 6 => "Action (High speed)",
+        Portrait = 7 => "Portrait",
+        Landscape = 8 => "Landscape",
+        Bulb = 9 => "Bulb",
+    }
+}
+
+exif_enum! {
+    /// Whether the white balance was set automatically (`WhiteBalance`).
+    WhiteBalance {
+        Auto = 0 => "Auto",
+        Manual = 1 => "Manual",
+    }
+}
+
+exif_enum! {
+    /// Whether the exposure was set automatically or bracketed (`ExposureMode`).
+    ExposureMode {
+        Auto = 0 => "Auto",
+        Manual = 1 => "Manual",
+        AutoBracket = 2 => "Auto bracket",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_numeric_and_printed() {
+        let printed: Orientation = serde_json::from_value(json!("Rotate 90 CW")).unwrap();
+        let numeric: Orientation = serde_json::from_value(json!(6)).unwrap();
+        assert_eq!(printed, Orientation::Rotate90);
+        assert_eq!(numeric, Orientation::Rotate90);
+
+        let flash: Flash = serde_json::from_value(json!("Auto, Fired, Red-eye reduction")).unwrap();
+        assert_eq!(flash, Flash::AutoFiredRedEye);
+        assert_eq!(flash.value(), Some(0x59));
+        assert_eq!(flash.fired(), Some(true));
+        assert_eq!(Flash::OffDidNotFire.fired(), Some(false));
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        assert_eq!(Orientation::from_value(9), Orientation::Unknown(9));
+        assert_eq!("Unknown (9)".parse(), Ok(Orientation::Unknown(9)));
+        assert_eq!("Unknown".parse(), Ok(MeteringMode::Unspecified));
+        assert_eq!(
+            "Daylight".parse(),
+            Ok(WhiteBalance::Other("Daylight".to_string()))
+        );
+        assert!(serde_json::from_value::<Flash>(json!(-1)).is_err());
+    }
+
+    #[test]
+    fn test_orientation_transform() {
+        let orientation = Orientation::MirrorHorizontalRotate90;
+        assert_eq!(orientation.rotation_degrees(), 90);
+        assert!(orientation.is_mirrored());
+        assert_eq!(orientation.display_dimensions(4000, 3000), (3000, 4000));
+
+        assert_eq!(Orientation::MirrorVertical.rotation_degrees(), 180);
+        assert!(!Orientation::Rotate270.is_mirrored());
+        assert_eq!(
+            Orientation::Rotate180.display_dimensions(4000, 3000),
+            (4000, 3000)
+        );
+        assert_eq!(Orientation::Unknown(9).rotation_degrees(), 0);
+        assert!(!Orientation::Unknown(9).is_standard());
+
+        assert_eq!(
+            Orientation::from_rotation(-90),
+            Some(Orientation::Rotate270)
+        );
+        assert_eq!(Orientation::from_rotation(450), Some(Orientation::Rotate90));
+        assert_eq!(Orientation::from_rotation(45), None);
+    }
+
+    #[test]
+    fn test_exif_data_orientation() {
+        use crate::g2::ExifData;
+
+        let photo: ExifData = serde_json::from_value(json!({
+            "Image": {"Orientation": "Rotate 90 CW", "ImageWidth": 4000, "ImageHeight": 3000},
+        }))
+        .unwrap();
+        assert_eq!(photo.orientation(), Some(Orientation::Rotate90));
+        assert_eq!(photo.display_dimensions(), Some((3000, 4000)));
+
+        // Videos only have a rotation.
+        let video: ExifData = serde_json::from_value(json!({
+            "Video": {"Rotation": 270, "ImageWidth": 1920, "ImageHeight": 1080},
+        }))
+        .unwrap();
+        assert_eq!(video.orientation(), Some(Orientation::Rotate270));
+        assert_eq!(video.dimensions(), Some((1920, 1080)));
+        assert_eq!(video.display_dimensions(), Some((1080, 1920)));
+    }
+
+    #[test]
+    fn test_display_and_serialize() {
+        assert_eq!(
+            ExposureProgram::AperturePriority.to_string(),
+            "Aperture-priority AE"
+        );
+        assert_eq!(Orientation::Unknown(9).to_string(), "Unknown (9)");
+        assert_eq!(
+            serde_json::to_value(ExposureMode::AutoBracket).unwrap(),
+            json!(2)
+        );
+        assert_eq!(
+            serde_json::to_value(WhiteBalance::Other("Daylight".to_string())).unwrap(),
+            json!("Daylight")
+        );
+    }
+}
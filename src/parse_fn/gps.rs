@@ -0,0 +1,231 @@
+use serde::Deserializer;
+
+/// A GPS position in decimal degrees, negative for south and west. The altitude is in meters,
+/// negative below sea level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsCoordinate {
+    /// Latitude in decimal degrees, negative for south.
+    pub lat: f64,
+    /// Longitude in decimal degrees, negative for west.
+    pub lon: f64,
+    /// Altitude in meters, negative below sea level, if known.
+    pub alt: Option<f64>,
+}
+
+/// Parses a latitude or longitude, either in exiftool's DMS format (`52 deg 22' 12.34" N`,
+/// also without seconds or the hemisphere) or as decimal degrees (`52.37`, `-4.89`).
+///
+/// A trailing `N`/`S`/`E`/`W` (or `North`, ...) sets the sign.
+pub fn parse_dms(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let numbers: Vec<f64> = s
+        .split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .filter(|part| !part.is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let (degrees, minutes, seconds) = match numbers[..] {
+        [d] => (d, 0.0, 0.0),
+        [d, m] => (d, m, 0.0),
+        [d, m, s] => (d, m, s),
+        _ => return None,
+    };
+    let value = degrees.abs() + minutes / 60.0 + seconds / 3600.0;
+    let negative = degrees.is_sign_negative() || is_negative_ref(hemisphere(s));
+    Some(if negative { -value } else { value })
+}
+
+/// Parses an altitude like `12.3 m Above Sea Level`, `5 m Below Sea Level`, `12.3 m` or `12.3`.
+pub fn parse_altitude(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let number = s.split_whitespace().next()?.parse::<f64>().ok()?;
+    Some(if s.contains("Below") {
+        -number.abs()
+    } else {
+        number
+    })
+}
+
+/// Parses a combined position as written to `GPSPosition` or `GPSCoordinates`: comma-separated
+/// DMS values (`52 deg 22' 12.34" N, 4 deg 53' 30.00" E[, 12.3 m Above Sea Level]`) or, with
+/// `-n`, space-separated decimals (`52.37 4.89[ 12.3]`).
+pub fn parse_position(s: &str) -> Option<GpsCoordinate> {
+    let parts: Vec<&str> = if s.contains(',') {
+        s.split(',').map(str::trim).collect()
+    } else {
+        s.split_whitespace().collect()
+    };
+    match parts[..] {
+        [lat, lon] => Some(GpsCoordinate {
+            lat: parse_dms(lat)?,
+            lon: parse_dms(lon)?,
+            alt: None,
+        }),
+        [lat, lon, alt] => Some(GpsCoordinate {
+            lat: parse_dms(lat)?,
+            lon: parse_dms(lon)?,
+            alt: Some(parse_altitude(alt)?),
+        }),
+        _ => None,
+    }
+}
+
+/// Applies a `GPSLatitudeRef`, `GPSLongitudeRef` or `GPSAltitudeRef` to an unsigned value:
+/// `S`/`South`, `W`/`West`, `Below Sea Level` and `1` (with `-n`) make it negative.
+pub fn apply_ref(value: f64, reference: Option<&str>) -> f64 {
+    match reference.map(str::trim) {
+        Some(r) if is_negative_ref(Some(r)) || r.contains("Below") || r == "1" => -value.abs(),
+        _ => value,
+    }
+}
+
+/// The trailing hemisphere word of a DMS string, e.g. `N` or `South`.
+fn hemisphere(s: &str) -> Option<&str> {
+    s.rsplit(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .filter(|word| !word.is_empty())
+}
+
+fn is_negative_ref(reference: Option<&str>) -> bool {
+    matches!(
+        reference.map(str::to_ascii_lowercase).as_deref(),
+        Some("s" | "south" | "w" | "west")
+    )
+}
+
+/// Deserializes a latitude or longitude from a DMS string or a `-n` number, see [`parse_dms`].
+///
+/// Only the value itself is used: without a hemisphere in the string (e.g. with `-n`), apply
+/// the `GPSLatitudeRef`/`GPSLongitudeRef` tag with [`apply_ref`], or read the composite
+/// `GPSLatitude`, which is already signed.
+pub fn degrees<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    super::parse_value(deserializer, "GPS coordinate", |s| {
+        parse_dms(s).ok_or_else(|| format!("invalid GPS coordinate: {}", s))
+    })
+}
+
+/// Deserializes an altitude in meters, see [`parse_altitude`].
+pub fn altitude<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    super::parse_value(deserializer, "GPS altitude", |s| {
+        parse_altitude(s).ok_or_else(|| format!("invalid GPS altitude: {}", s))
+    })
+}
+
+/// Deserializes a combined `GPSPosition` or `GPSCoordinates` value, see [`parse_position`].
+pub fn position<'de, D>(deserializer: D) -> Result<Option<GpsCoordinate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    super::parse_value(deserializer, "GPS position", |s| {
+        parse_position(s).ok_or_else(|| format!("invalid GPS position: {}", s))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("value should parse");
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_dms() {
+        assert_close(parse_dms(r#"52 deg 22' 12.00" N"#), 52.37);
+        assert_close(parse_dms(r#"33 deg 51' 36.00" S"#), -33.86);
+        assert_close(parse_dms(r#"4 deg 53' 24.00" West"#), -4.89);
+        assert_close(parse_dms(r#"52 deg 22.2' "#), 52.37);
+        assert_close(parse_dms("-33.86"), -33.86);
+        assert_eq!(parse_dms("unknown"), None);
+    }
+
+    #[test]
+    fn test_parse_altitude() {
+        assert_close(parse_altitude("12.3 m Above Sea Level"), 12.3);
+        assert_close(parse_altitude("5 m Below Sea Level"), -5.0);
+        assert_close(parse_altitude("7"), 7.0);
+    }
+
+    #[test]
+    fn test_parse_position() {
+        let position =
+            parse_position(r#"52 deg 22' 12.00" N, 4 deg 53' 24.00" W, 5 m Below Sea Level"#)
+                .unwrap();
+        assert_close(Some(position.lat), 52.37);
+        assert_close(Some(position.lon), -4.89);
+        assert_close(position.alt, -5.0);
+
+        let position = parse_position("52.37 -4.89").unwrap();
+        assert_eq!(
+            position,
+            GpsCoordinate {
+                lat: 52.37,
+                lon: -4.89,
+                alt: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserializers() {
+        use serde::Deserialize;
+        use serde_json::json;
+
+        #[derive(Deserialize)]
+        struct Photo {
+            #[serde(deserialize_with = "degrees", default)]
+            lat: Option<f64>,
+            #[serde(deserialize_with = "degrees", default)]
+            lon: Option<f64>,
+            #[serde(deserialize_with = "altitude", default)]
+            alt: Option<f64>,
+            #[serde(deserialize_with = "position", default)]
+            position: Option<GpsCoordinate>,
+        }
+
+        let photo: Photo = serde_json::from_value(json!({
+            "lat": "33 deg 51' 36.00\" S",
+            "lon": 151.21,
+            "alt": "5 m Below Sea Level",
+            "position": "52.37 4.89",
+        }))
+        .unwrap();
+        assert_close(photo.lat, -33.86);
+        assert_close(photo.lon, 151.21);
+        assert_close(photo.alt, -5.0);
+        assert_eq!(
+            photo.position,
+            Some(GpsCoordinate {
+                lat: 52.37,
+                lon: 4.89,
+                alt: None
+            })
+        );
+
+        let photo: Photo = serde_json::from_value(json!({ "lat": "", "alt": "undef" })).unwrap();
+        assert_eq!(photo.lat, None);
+        assert_eq!(photo.alt, None);
+        assert!(serde_json::from_value::<Photo>(json!({ "lat": "north" })).is_err());
+    }
+
+    #[test]
+    fn test_apply_ref() {
+        assert_eq!(apply_ref(52.37, Some("North")), 52.37);
+        assert_eq!(apply_ref(4.89, Some("W")), -4.89);
+        assert_eq!(apply_ref(5.0, Some("Below Sea Level")), -5.0);
+        assert_eq!(apply_ref(5.0, Some("1")), -5.0);
+        assert_eq!(apply_ref(5.0, None), 5.0);
+    }
+}
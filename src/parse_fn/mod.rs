@@ -1,9 +1,47 @@
 pub mod array_or_int;
 pub mod date;
 pub mod datetime;
+pub mod enums;
+pub mod gps;
+pub mod rational;
 pub mod space_sep;
 pub mod string;
 pub mod string_list;
 pub mod time;
 pub mod u32;
 pub mod undef_or_float;
+pub mod units;
+
+use serde::{
+    de::{self, Deserializer},
+    Deserialize,
+};
+use serde_json::Value;
+use std::fmt;
+
+/// Deserializes a string or `-n` number with `parse`. Empty and `undef` values are `None`.
+///
+/// `expected` names the value in the error for other JSON types, e.g. `"rational"`.
+pub(crate) fn parse_value<'de, D, T, E>(
+    deserializer: D,
+    expected: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    E: fmt::Display,
+{
+    let s = match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) if s.is_empty() || s == "undef" => return Ok(None),
+        Some(Value::String(s)) => s,
+        Some(Value::Number(n)) => n.to_string(),
+        Some(Value::Null) | None => return Ok(None),
+        Some(other) => {
+            return Err(de::Error::custom(format!(
+                "unexpected type for {}: {:?}",
+                expected, other
+            )))
+        }
+    };
+    parse(&s).map(Some).map_err(de::Error::custom)
+}
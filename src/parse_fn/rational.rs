@@ -0,0 +1,282 @@
+//! Rational numbers as `exiftool` prints exposure values: `1/250`, `+1/3`, `0.5` or `30`.
+
+use serde::{
+    de::{self, Deserializer},
+    Deserialize,
+};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+// Largest denominator used when approximating decimals, e.g. `-n` exposure times.
+const MAX_DENOMINATOR: i64 = 1_000_000;
+
+/// A fraction like an exposure time (`1/250`) or exposure compensation (`-2/3`), always in
+/// lowest terms with a positive denominator, so equal values compare and hash equal.
+///
+/// Parses fractions and decimals, and converts decimals (like `0.004` returned with `-n`) to
+/// the nearest simple fraction. [`Display`](fmt::Display) prints `num/den`, or only `num` if
+/// the denominator is 1, with a sign only for negative values. `exiftool` accepts that form
+/// when writing, e.g. with [`ExifTool::write_tag`](crate::ExifTool::write_tag), but prints
+/// positive exposure compensation with a `+` (`+1/3` reads back as `1/3`).
+///
+/// # Examples
+///
+/// ```
+/// use exiftool::parse_fn::rational::Rational;
+/// use std::time::Duration;
+///
+/// let exposure: Rational = "0.004".parse().unwrap();
+/// assert_eq!(exposure, Rational::new(1, 250));
+/// assert_eq!((exposure.num(), exposure.den()), (1, 250));
+/// assert_eq!(exposure.to_string(), "1/250");
+/// assert_eq!(exposure.to_duration(), Some(Duration::from_millis(4)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+/// Error returned when a string isn't a fraction or decimal number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRationalError(String);
+
+impl fmt::Display for ParseRationalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rational number: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRationalError {}
+
+// In `i128`, so `i64::MIN` has an absolute value.
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Rational {
+    /// Creates a fraction and reduces it to lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is zero, or if the reduced fraction doesn't fit in `i64`, which only
+    /// happens for a numerator or denominator of `i64::MIN` with a negative denominator
+    /// (e.g. `i64::MIN / -1`). Use [`Rational::checked_new`] for untrusted input.
+    pub fn new(num: i64, den: i64) -> Self {
+        assert!(den != 0, "denominator must not be zero");
+        Self::checked_new(num, den).expect("reduced fraction overflows i64")
+    }
+
+    /// Like [`Rational::new`], but returns `None` instead of panicking.
+    pub fn checked_new(num: i64, den: i64) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let (num, den) = (i128::from(num), i128::from(den));
+        let divisor = gcd(num, den) * den.signum();
+        Some(Rational {
+            num: i64::try_from(num / divisor).ok()?,
+            den: i64::try_from(den / divisor).ok()?,
+        })
+    }
+
+    /// The nearest fraction with a denominator of at most one million. Returns `None` for
+    /// infinite and NaN values.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() || value.abs() > i64::MAX as f64 {
+            return None;
+        }
+        // Continued fraction expansion, stopping once the approximation is exact enough.
+        let (mut h0, mut h1, mut k0, mut k1) = (0i64, 1i64, 1i64, 0i64);
+        let mut x = value;
+        loop {
+            let a = x.floor();
+            let next = |p: i64, q: i64| (a as i64).checked_mul(p)?.checked_add(q);
+            let (Some(h2), Some(k2)) = (next(h1, h0), next(k1, k0)) else {
+                break;
+            };
+            if k2 > MAX_DENOMINATOR {
+                break;
+            }
+            (h0, h1, k0, k1) = (h1, h2, k1, k2);
+            let error = (value - h1 as f64 / k1 as f64).abs();
+            if error <= 1e-9 * value.abs().max(1e-9) || x == a {
+                break;
+            }
+            x = 1.0 / (x - a);
+        }
+        Rational::checked_new(h1, k1)
+    }
+
+    /// The numerator; negative for negative values.
+    pub fn num(self) -> i64 {
+        self.num
+    }
+
+    /// The denominator, always positive.
+    pub fn den(self) -> i64 {
+        self.den
+    }
+
+    /// The value as a floating point number.
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// The value as a duration in seconds, e.g. for exposure times. `None` if negative.
+    pub fn to_duration(self) -> Option<Duration> {
+        Duration::try_from_secs_f64(self.to_f64()).ok()
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl FromStr for Rational {
+    type Err = ParseRationalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseRationalError(s.to_string());
+        let trimmed = s.trim();
+        match trimmed.split_once('/') {
+            Some((num, den)) => {
+                let num = num.trim().parse::<i64>().map_err(|_| error())?;
+                let den = den.trim().parse::<i64>().map_err(|_| error())?;
+                Rational::checked_new(num, den).ok_or_else(error)
+            }
+            None => trimmed
+                .parse::<f64>()
+                .ok()
+                .and_then(Rational::from_f64)
+                .ok_or_else(error),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Rational {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        rational(deserializer)?.ok_or_else(|| de::Error::custom("missing rational number"))
+    }
+}
+
+/// Deserializes an optional [`Rational`] from a fraction string or a number. Empty and `undef`
+/// values are `None`.
+pub fn rational<'de, D>(deserializer: D) -> Result<Option<Rational>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    super::parse_value(deserializer, "rational", str::parse)
+}
+
+/// Deserializes a number that may also be written as a fraction (e.g. APEX values) into an
+/// `f64`.
+pub fn float<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    super::parse_value(deserializer, "float", |s| {
+        s.parse::<f64>()
+            .or_else(|_| s.parse::<Rational>().map(Rational::to_f64))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1/250".parse(), Ok(Rational::new(1, 250)));
+        assert_eq!("+1/3".parse(), Ok(Rational::new(1, 3)));
+        assert_eq!("-2/3".parse(), Ok(Rational::new(-2, 3)));
+        assert_eq!("10/20".parse(), Ok(Rational::new(1, 2)));
+        assert_eq!("0.5".parse(), Ok(Rational::new(1, 2)));
+        assert_eq!("30".parse(), Ok(Rational::new(30, 1)));
+        assert_eq!("1.3".parse(), Ok(Rational::new(13, 10)));
+        assert!("1/0".parse::<Rational>().is_err());
+        assert!("fast".parse::<Rational>().is_err());
+
+        let half: Rational = "-2/-4".parse().unwrap();
+        assert_eq!((half.num(), half.den()), (1, 2));
+        assert_eq!(half.to_string(), "1/2");
+        assert_eq!("+1/3".parse::<Rational>().unwrap().to_string(), "1/3");
+    }
+
+    #[test]
+    fn test_parse_extremes() {
+        assert_eq!(
+            "-9223372036854775808/-1".parse::<Rational>(),
+            Err(ParseRationalError("-9223372036854775808/-1".to_string()))
+        );
+        assert_eq!("0/-9223372036854775808".parse(), Ok(Rational::new(0, 1)));
+        assert_eq!(
+            "-9223372036854775808/-2".parse(),
+            Ok(Rational::new(1 << 62, 1))
+        );
+        assert_eq!(
+            "-9223372036854775808/1".parse(),
+            Ok(Rational::new(i64::MIN, 1))
+        );
+        assert_eq!(Rational::checked_new(i64::MIN, -1), None);
+        assert_eq!(Rational::checked_new(1, 0), None);
+        assert_eq!(
+            Rational::from_f64(-9.2e18),
+            Some(Rational::new(-9_200_000_000_000_000_000, 1))
+        );
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(Rational::from_f64(0.004), Some(Rational::new(1, 250)));
+        assert_eq!(
+            Rational::from_f64(0.003_921_568_627_450_98),
+            Some(Rational::new(1, 255))
+        );
+        assert_eq!(
+            Rational::from_f64(-0.333_333_333_3),
+            Some(Rational::new(-1, 3))
+        );
+        assert_eq!(Rational::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for s in ["1/250", "-2/3", "0", "30"] {
+            assert_eq!(s.parse::<Rational>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        let exposure = Rational::new(1, 4);
+        assert_eq!(exposure.to_f64(), 0.25);
+        assert_eq!(exposure.to_duration(), Some(Duration::from_millis(250)));
+        assert_eq!(Rational::new(-1, 3).to_duration(), None);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let value: Rational = serde_json::from_value(json!("1/100")).unwrap();
+        assert_eq!(value, Rational::new(1, 100));
+        let value: Rational = serde_json::from_value(json!(0.01)).unwrap();
+        assert_eq!(value, Rational::new(1, 100));
+        let value: Option<f64> = float(json!("1/2")).unwrap();
+        assert_eq!(value, Some(0.5));
+    }
+}
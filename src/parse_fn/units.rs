@@ -0,0 +1,304 @@
+//! Newtypes for quantities that `exiftool` prints with a unit, like `4.7 mm` or `2.1 MB`.
+//!
+//! Each type deserializes from the print-converted string as well as from the plain number
+//! returned with `-n`.
+
+use log::warn;
+use serde::{
+    de::{self, Deserializer},
+    Deserialize,
+};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A length in millimeters, e.g. a focal length (`4.7 mm`).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Millimeters(pub f64);
+
+/// A bit rate in bits per second (`12.3 Mbps`). Prefixes are decimal, like `exiftool` uses them.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Bitrate(pub f64);
+
+/// A size in bytes (`2.1 MB`). `kB`, `MB`, `GB` and `TB` are decimal (1 kB = 1000 bytes),
+/// `KiB`, `MiB`, `GiB` and `TiB` binary (1 KiB = 1024 bytes). Sizes parsed from
+/// print-converted values are rounded to whole bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub u64);
+
+/// A percentage (`100.00%` is `Percent(100.0)`).
+///
+/// With `-n`, `exiftool` returns volume tags as fractions (`1` for 100%), so numbers are
+/// multiplied by 100.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Percent(pub f64);
+
+/// Error returned when a value with a unit can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseUnitError {
+    expected: &'static str,
+    value: String,
+}
+
+impl fmt::Display for ParseUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {}: {}", self.expected, self.value)
+    }
+}
+
+impl std::error::Error for ParseUnitError {}
+
+impl ParseUnitError {
+    fn new(expected: &'static str, value: &str) -> Self {
+        Self {
+            expected,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Splits `"12.3 Mbps"` into `(12.3, "Mbps")`, also without the space.
+fn split_unit(s: &str) -> Option<(f64, &str)> {
+    let s = s.trim();
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')))
+        .unwrap_or(s.len());
+    let number = s[..end].parse().ok()?;
+    Some((number, s[end..].trim()))
+}
+
+impl FromStr for Millimeters {
+    type Err = ParseUnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_unit(s) {
+            Some((value, "mm" | "")) => Ok(Millimeters(value)),
+            _ => Err(ParseUnitError::new("length in mm", s)),
+        }
+    }
+}
+
+impl FromStr for Bitrate {
+    type Err = ParseUnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, unit) = split_unit(s).ok_or_else(|| ParseUnitError::new("bit rate", s))?;
+        let factor = match unit {
+            "" | "bps" => 1.0,
+            "kbps" => 1e3,
+            "Mbps" => 1e6,
+            "Gbps" => 1e9,
+            _ => return Err(ParseUnitError::new("bit rate", s)),
+        };
+        Ok(Bitrate(value * factor))
+    }
+}
+
+impl FromStr for Bytes {
+    type Err = ParseUnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, unit) = split_unit(s).ok_or_else(|| ParseUnitError::new("size", s))?;
+        let factor: f64 = match unit {
+            "" | "bytes" | "byte" | "B" => 1.0,
+            "kB" | "KB" => 1e3,
+            "MB" => 1e6,
+            "GB" => 1e9,
+            "TB" => 1e12,
+            "KiB" => 1024.0,
+            "MiB" => 1024.0 * 1024.0,
+            "GiB" => 1024.0 * 1024.0 * 1024.0,
+            "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            _ => return Err(ParseUnitError::new("size", s)),
+        };
+        if value < 0.0 {
+            return Err(ParseUnitError::new("size", s));
+        }
+        Ok(Bytes((value * factor).round() as u64))
+    }
+}
+
+impl FromStr for Percent {
+    type Err = ParseUnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_unit(s) {
+            Some((value, "%")) => Ok(Percent(value)),
+            // Numbers without a unit are `-n` fractions.
+            Some((value, "")) => Ok(Percent(value * 100.0)),
+            _ => Err(ParseUnitError::new("percentage", s)),
+        }
+    }
+}
+
+impl Percent {
+    /// The percentage as a fraction, e.g. `1.0` for 100%.
+    pub fn fraction(self) -> f64 {
+        self.0 / 100.0
+    }
+}
+
+/// Parses a duration as printed by `exiftool`: `12.50 s`, `0:02:26`, `1 days 2:03:04`, each
+/// optionally followed by ` (approx)`, or a plain number of seconds (`-n`).
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim().trim_end_matches("(approx)").trim();
+    if let Some((seconds, "s")) = split_unit(s) {
+        return seconds_to_duration(seconds);
+    }
+
+    let (days, clock) = match s.split_once("day") {
+        Some((days, rest)) => (
+            days.trim().parse::<f64>().ok()?,
+            rest.trim_start_matches('s').trim(),
+        ),
+        None => (0.0, s),
+    };
+    let mut seconds = 0.0;
+    for part in clock.split(':') {
+        seconds = seconds * 60.0 + part.trim().parse::<f64>().ok()?;
+    }
+    seconds_to_duration(days * 86400.0 + seconds)
+}
+
+fn seconds_to_duration(seconds: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+macro_rules! impl_deserialize {
+    ($($ty:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    super::parse_value(deserializer, "a value with unit", str::parse::<$ty>)?
+                        .ok_or_else(|| de::Error::custom(concat!("missing ", stringify!($ty))))
+                }
+            }
+        )*
+    };
+}
+
+impl_deserialize!(Millimeters, Bitrate, Bytes, Percent);
+
+/// Generic deserializer for optional fields of the types in this module. Unlike deriving,
+/// it also maps empty and `undef` values to `None`, as well as values that can't be parsed
+/// (logged as a warning), so one odd tag doesn't fail the whole struct.
+pub fn optional<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = ParseUnitError>,
+{
+    let value = super::parse_value(deserializer, "a value with unit", |s| {
+        Ok::<_, Infallible>(s.parse::<T>().map_err(|e| warn!("Ignoring {}", e)).ok())
+    })?;
+    Ok(value.flatten())
+}
+
+/// Deserializes a [`Duration`], see [`parse_duration`]. Like [`optional`], values that can't
+/// be parsed (e.g. negative ones) are logged and become `None`.
+pub fn duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = super::parse_value(deserializer, "duration", |s| {
+        let duration = parse_duration(s);
+        if duration.is_none() {
+            warn!("Ignoring invalid duration: {}", s);
+        }
+        Ok::<_, Infallible>(duration)
+    })?;
+    Ok(value.flatten())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!("4.7 mm".parse(), Ok(Millimeters(4.7)));
+        assert_eq!("4.7".parse(), Ok(Millimeters(4.7)));
+        assert_eq!("12.3 Mbps".parse(), Ok(Bitrate(12_300_000.0)));
+        assert_eq!("256 kbps".parse(), Ok(Bitrate(256_000.0)));
+        assert_eq!("2.5 MB".parse(), Ok(Bytes(2_500_000)));
+        assert_eq!("2.5 MiB".parse(), Ok(Bytes(2_621_440)));
+        assert_eq!("3 kB".parse(), Ok(Bytes(3_000)));
+        assert_eq!("3 KiB".parse(), Ok(Bytes(3_072)));
+        assert_eq!("1 TB".parse(), Ok(Bytes(1_000_000_000_000)));
+        assert_eq!("512 bytes".parse(), Ok(Bytes(512)));
+        assert_eq!("100.00%".parse(), Ok(Percent(100.0)));
+        assert_eq!("0.5".parse(), Ok(Percent(50.0)));
+        assert!("4.7 m".parse::<Millimeters>().is_err());
+        assert!("lots".parse::<Bytes>().is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            parse_duration("12.50 s"),
+            Some(Duration::from_millis(12500))
+        );
+        assert_eq!(parse_duration("0:02:26"), Some(Duration::from_secs(146)));
+        assert_eq!(
+            parse_duration("1 days 2:03:04 (approx)"),
+            Some(Duration::from_secs(93784))
+        );
+        assert_eq!(parse_duration("146.5"), Some(Duration::from_millis(146500)));
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration("-1.5 s"), None);
+    }
+
+    #[test]
+    fn test_deserialize_numbers() {
+        #[derive(Deserialize)]
+        struct Video {
+            #[serde(deserialize_with = "optional", default)]
+            size: Option<Bytes>,
+            #[serde(deserialize_with = "duration", default)]
+            duration: Option<Duration>,
+            volume: Option<Percent>,
+        }
+        let video: Video = serde_json::from_value(json!({
+            "size": 2048,
+            "duration": 146.5,
+            "volume": 1,
+        }))
+        .unwrap();
+        assert_eq!(video.size, Some(Bytes(2048)));
+        assert_eq!(video.duration, Some(Duration::from_millis(146500)));
+        assert_eq!(video.volume, Some(Percent(100.0)));
+
+        let video: Video = serde_json::from_value(json!({ "size": "undef" })).unwrap();
+        assert_eq!(video.size, None);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_is_none() {
+        #[derive(Deserialize)]
+        struct Video {
+            #[serde(deserialize_with = "optional", default)]
+            size: Option<Bytes>,
+            #[serde(deserialize_with = "duration", default)]
+            duration: Option<Duration>,
+            #[serde(deserialize_with = "duration", default)]
+            poster_time: Option<Duration>,
+        }
+        let video: Video = serde_json::from_value(json!({
+            "size": "lots",
+            "duration": "-1.5 s",
+            "poster_time": "0 s",
+        }))
+        .unwrap();
+        assert_eq!(video.size, None);
+        assert_eq!(video.duration, None);
+        assert_eq!(video.poster_time, Some(Duration::ZERO));
+
+        // Without `optional`, the parse error is reported.
+        let error = serde_json::from_value::<Bytes>(json!("lots")).unwrap_err();
+        assert_eq!(error.to_string(), "invalid size: lots");
+    }
+}
@@ -0,0 +1,339 @@
+use crate::builder::ExifToolBuilder;
+use crate::error::ExifToolError;
+use crate::exiftool::ExifTool;
+use log::warn;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+/// A thread-safe pool of persistent [`ExifTool`] processes.
+///
+/// Every method on [`ExifTool`] takes `&mut self`, so a single instance can only serve one
+/// command at a time. The pool spawns a fixed number of `exiftool` workers up front and hands
+/// them out as [`PooledExifTool`] guards, which dereference to [`ExifTool`] and return the
+/// worker to the pool when dropped.
+///
+/// `ExifToolPool` is `Send + Sync`, so it can be shared between threads by reference or
+/// wrapped in an [`Arc`](std::sync::Arc).
+///
+/// Workers whose process has died (for example after [`ExifToolError::ProcessTerminated`])
+/// are not returned to the pool. Their slot is refilled with a freshly spawned process the
+/// next time a worker is requested.
+///
+/// # Examples
+///
+/// ```no_run
+/// use exiftool::{ExifToolError, ExifToolPool};
+/// use std::path::Path;
+/// use std::thread;
+///
+/// fn main() -> Result<(), ExifToolError> {
+///     let pool = ExifToolPool::new(4)?;
+///
+///     thread::scope(|s| {
+///         for _ in 0..8 {
+///             s.spawn(|| -> Result<(), ExifToolError> {
+///                 // Blocks until one of the 4 workers is free.
+///                 let mut et = pool.get()?;
+///                 let width: u32 = et.read_tag(Path::new("image.jpg"), "ImageWidth")?;
+///                 println!("Width: {}", width);
+///                 Ok(())
+///             });
+///         }
+///     });
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ExifToolPool {
+    builder: ExifToolBuilder,
+    size: usize,
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    /// Workers that are ready to be checked out.
+    idle: Vec<ExifTool>,
+    /// Number of slots whose worker died and still has to be respawned.
+    vacant: usize,
+}
+
+impl ExifToolPool {
+    /// Launches a pool of `size` `exiftool` processes using the default system path.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while spawning the workers, see
+    /// [`ExifTool::with_executable`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifToolError, ExifToolPool};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let pool = ExifToolPool::new(4)?;
+    /// assert_eq!(pool.size(), 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(size: usize) -> Result<Self, ExifToolError> {
+        Self::with_executable(Path::new("exiftool"), size)
+    }
+
+    /// Launches a pool of `size` `exiftool` processes from a specific executable path.
+    ///
+    /// # Arguments
+    ///
+    /// * `exiftool_path` - The path to the `exiftool` executable file.
+    /// * `size` - The number of worker processes to keep running.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while spawning the workers, see
+    /// [`ExifTool::with_executable`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn with_executable(exiftool_path: &Path, size: usize) -> Result<Self, ExifToolError> {
+        Self::with_builder(ExifToolBuilder::new().executable(exiftool_path), size)
+    }
+
+    /// Launches a pool of `size` `exiftool` processes, each configured by `builder`.
+    ///
+    /// Replacement workers are spawned with the same configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while spawning the workers, see
+    /// [`ExifToolBuilder::build`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exiftool::{ExifTool, ExifToolError, ExifToolPool};
+    ///
+    /// # fn main() -> Result<(), ExifToolError> {
+    /// let pool = ExifToolPool::with_builder(ExifTool::builder().charset("filename=utf8"), 4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_builder(builder: ExifToolBuilder, size: usize) -> Result<Self, ExifToolError> {
+        assert!(size > 0, "ExifToolPool size must be at least 1");
+
+        let idle = (0..size)
+            .map(|_| builder.build())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            builder,
+            size,
+            state: Mutex::new(PoolState { idle, vacant: 0 }),
+            available: Condvar::new(),
+        })
+    }
+
+    /// The number of worker processes managed by this pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Checks out a worker, blocking until one becomes available.
+    ///
+    /// If a worker slot is vacant because its process died, a new `exiftool` process is
+    /// spawned to fill it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error from [`ExifTool::with_executable`] if a replacement worker could not
+    /// be spawned. The slot stays vacant and spawning is retried on the next checkout.
+    pub fn get(&self) -> Result<PooledExifTool<'_>, ExifToolError> {
+        let mut state = self.lock_state();
+        loop {
+            if let Some(exiftool) = state.idle.pop() {
+                return Ok(self.guard(exiftool));
+            }
+            if state.vacant > 0 {
+                state.vacant -= 1;
+                drop(state);
+                return self.respawn().map(|exiftool| self.guard(exiftool));
+            }
+            state = self
+                .available
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    /// Checks out a worker if one is available right now, without blocking.
+    ///
+    /// Returns `Ok(None)` when all workers are currently checked out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error from [`ExifTool::with_executable`] if a replacement worker could not
+    /// be spawned.
+    pub fn try_get(&self) -> Result<Option<PooledExifTool<'_>>, ExifToolError> {
+        let mut state = self.lock_state();
+        if let Some(exiftool) = state.idle.pop() {
+            return Ok(Some(self.guard(exiftool)));
+        }
+        if state.vacant > 0 {
+            state.vacant -= 1;
+            drop(state);
+            return self.respawn().map(|exiftool| Some(self.guard(exiftool)));
+        }
+        Ok(None)
+    }
+
+    fn guard(&self, exiftool: ExifTool) -> PooledExifTool<'_> {
+        PooledExifTool {
+            pool: self,
+            exiftool: Some(exiftool),
+        }
+    }
+
+    /// Spawns a replacement worker. On failure the slot is marked vacant again.
+    fn respawn(&self) -> Result<ExifTool, ExifToolError> {
+        self.builder.build().inspect_err(|_| {
+            self.lock_state().vacant += 1;
+            self.available.notify_one();
+        })
+    }
+
+    /// Returns a worker to the pool, discarding it if its process is no longer running.
+    fn release(&self, mut exiftool: ExifTool) {
+        let alive = exiftool.is_alive();
+        let mut state = self.lock_state();
+        if alive {
+            state.idle.push(exiftool);
+        } else {
+            warn!("Discarding terminated exiftool worker, it will be respawned on next use.");
+            state.vacant += 1;
+        }
+        drop(state);
+        self.available.notify_one();
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, PoolState> {
+        // A panic while holding the lock can't leave the state inconsistent, so recover.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A worker checked out from an [`ExifToolPool`].
+///
+/// Dereferences to [`ExifTool`], so all of its methods can be called directly.
+/// The worker is returned to the pool when this guard is dropped.
+#[derive(Debug)]
+pub struct PooledExifTool<'a> {
+    pool: &'a ExifToolPool,
+    exiftool: Option<ExifTool>,
+}
+
+impl Deref for PooledExifTool<'_> {
+    type Target = ExifTool;
+
+    fn deref(&self) -> &Self::Target {
+        self.exiftool
+            .as_ref()
+            .expect("worker is only taken on drop")
+    }
+}
+
+impl DerefMut for PooledExifTool<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.exiftool
+            .as_mut()
+            .expect("worker is only taken on drop")
+    }
+}
+
+impl Drop for PooledExifTool<'_> {
+    fn drop(&mut self) {
+        if let Some(exiftool) = self.exiftool.take() {
+            self.pool.release(exiftool);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_helpers::test_image_path;
+    use assert_matches::assert_matches;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_pool_is_send_sync() {
+        assert_send_sync::<ExifToolPool>();
+    }
+
+    #[test]
+    fn test_pool_invalid_path() {
+        let result = ExifToolPool::with_executable(Path::new("nonexistent_exiftool_command"), 2);
+        assert_matches!(result, Err(ExifToolError::ExifToolNotFound(_)));
+    }
+
+    #[test]
+    fn test_pool_concurrent_reads() -> Result<(), ExifToolError> {
+        let pool = ExifToolPool::new(3)?;
+        let path = test_image_path();
+
+        thread::scope(|s| {
+            let handles: Vec<_> = (0..12)
+                .map(|_| {
+                    s.spawn(|| -> Result<String, ExifToolError> {
+                        let mut et = pool.get()?;
+                        et.read_tag(&path, "Make")
+                    })
+                })
+                .collect();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap().unwrap(), "Huawei");
+            }
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_try_get_exhausted() -> Result<(), ExifToolError> {
+        let pool = ExifToolPool::new(1)?;
+        let first = pool.get()?;
+        assert!(pool.try_get()?.is_none());
+        drop(first);
+        assert!(pool.try_get()?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_replaces_terminated_worker() -> Result<(), ExifToolError> {
+        let pool = ExifToolPool::new(1)?;
+        {
+            let mut et = pool.get()?;
+            // Ask the worker to leave stay-open mode, which terminates the process.
+            let result = et.execute_raw(&["-stay_open", "False"]);
+            assert_matches!(result, Err(ExifToolError::ProcessTerminated));
+        }
+
+        let mut et = pool.get()?;
+        let make: String = et.read_tag(&test_image_path(), "Make")?;
+        assert_eq!(make, "Huawei");
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Which value wins when a tag exists both in a file and in its XMP sidecar, used by
+/// [`ExifTool::json_with_sidecar`](crate::ExifTool::json_with_sidecar).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SidecarPrecedence {
+    /// Sidecar values replace embedded ones, as sidecars usually hold the newer edits.
+    #[default]
+    Sidecar,
+    /// Embedded values are kept; the sidecar only adds tags the file doesn't have.
+    Embedded,
+}
+
+/// How the XMP sidecar of a file is named, set with
+/// [`ExifTool::set_sidecar_naming`](crate::ExifTool::set_sidecar_naming).
+///
+/// # Examples
+///
+/// ```
+/// use exiftool::SidecarNaming;
+/// use std::path::{Path, PathBuf};
+///
+/// let raw = Path::new("photos/IMG_0001.CR2");
+/// assert_eq!(
+///     SidecarNaming::ReplaceExtension.path(raw),
+///     PathBuf::from("photos/IMG_0001.xmp")
+/// );
+/// assert_eq!(
+///     SidecarNaming::AppendExtension.path(raw),
+///     PathBuf::from("photos/IMG_0001.CR2.xmp")
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SidecarNaming {
+    /// `IMG_0001.xmp` for `IMG_0001.CR2` (`-o %d%f.xmp`), the Adobe convention.
+    ///
+    /// Files that only differ in their extension, like `IMG_0001.CR2` and `IMG_0001.JPG`,
+    /// share one sidecar.
+    #[default]
+    ReplaceExtension,
+    /// `IMG_0001.CR2.xmp` for `IMG_0001.CR2` (`-o %d%f.%e.xmp`), as used by darktable.
+    /// Every file gets its own sidecar.
+    AppendExtension,
+}
+
+impl SidecarNaming {
+    /// The `-o` file name format that creates a sidecar with this naming.
+    pub fn format(self) -> &'static str {
+        match self {
+            SidecarNaming::ReplaceExtension => "%d%f.xmp",
+            SidecarNaming::AppendExtension => "%d%f.%e.xmp",
+        }
+    }
+
+    /// The path of a file's sidecar in the same directory.
+    pub fn path(self, file_path: &Path) -> PathBuf {
+        match self {
+            SidecarNaming::ReplaceExtension => file_path.with_extension("xmp"),
+            SidecarNaming::AppendExtension => {
+                let mut path = file_path.as_os_str().to_owned();
+                path.push(".xmp");
+                PathBuf::from(path)
+            }
+        }
+    }
+}
+
+/// Merges the tags of a sidecar's `-json` object into the file's object.
+///
+/// `SourceFile` always stays the file's path. Non-object values are returned unchanged.
+pub(crate) fn merge_sidecar(
+    mut embedded: Value,
+    sidecar: Value,
+    precedence: SidecarPrecedence,
+) -> Value {
+    let (Some(target), Value::Object(sidecar)) = (embedded.as_object_mut(), sidecar) else {
+        return embedded;
+    };
+    for (tag, value) in sidecar {
+        if tag == "SourceFile" {
+            continue;
+        }
+        match precedence {
+            SidecarPrecedence::Sidecar => {
+                target.insert(tag, value);
+            }
+            SidecarPrecedence::Embedded => {
+                target.entry(tag).or_insert(value);
+            }
+        }
+    }
+    embedded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sidecar_path() {
+        let raw = Path::new("photos/IMG_0001.CR2");
+        let jpg = Path::new("photos/IMG_0001.JPG");
+
+        // The default naming maps both files to the same sidecar.
+        assert_eq!(
+            SidecarNaming::default().path(raw),
+            PathBuf::from("photos/IMG_0001.xmp")
+        );
+        assert_eq!(
+            SidecarNaming::default().path(raw),
+            SidecarNaming::default().path(jpg)
+        );
+
+        assert_eq!(
+            SidecarNaming::AppendExtension.path(raw),
+            PathBuf::from("photos/IMG_0001.CR2.xmp")
+        );
+        assert_eq!(
+            SidecarNaming::AppendExtension.path(jpg),
+            PathBuf::from("photos/IMG_0001.JPG.xmp")
+        );
+        assert_eq!(SidecarNaming::AppendExtension.format(), "%d%f.%e.xmp");
+    }
+
+    #[test]
+    fn test_merge_sidecar() {
+        let embedded = json!({
+            "SourceFile": "IMG_0001.CR2",
+            "Make": "Canon",
+            "Rating": 1,
+        });
+        let sidecar = json!({
+            "SourceFile": "IMG_0001.xmp",
+            "Rating": 5,
+            "Label": "Red",
+        });
+
+        assert_eq!(
+            merge_sidecar(
+                embedded.clone(),
+                sidecar.clone(),
+                SidecarPrecedence::Sidecar
+            ),
+            json!({
+                "SourceFile": "IMG_0001.CR2",
+                "Make": "Canon",
+                "Rating": 5,
+                "Label": "Red",
+            })
+        );
+        assert_eq!(
+            merge_sidecar(embedded, sidecar, SidecarPrecedence::Embedded),
+            json!({
+                "SourceFile": "IMG_0001.CR2",
+                "Make": "Canon",
+                "Rating": 1,
+                "Label": "Red",
+            })
+        );
+    }
+}
@@ -1,6 +1,13 @@
 use crate::parse_fn::datetime::MaybeDateTime;
+use crate::parse_fn::enums::{
+    ExposureMode, ExposureProgram, Flash, MeteringMode, Orientation, WhiteBalance,
+};
+use crate::parse_fn::gps::{self, GpsCoordinate};
+use crate::parse_fn::rational::Rational;
+use crate::parse_fn::units::{Bitrate, Bytes, Millimeters, Percent};
 use chrono::NaiveTime;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
@@ -24,6 +31,49 @@ pub struct ExifData {
     pub video: Option<VideoMetadata>,
 }
 
+impl ExifData {
+    /// The effective display orientation.
+    ///
+    /// Uses the image `Orientation`, which `-g2` fills from EXIF or, if there's no EXIF value,
+    /// from XMP `tiff:Orientation`. Falls back to the video `Rotation` (QuickTime), and is `None`
+    /// if neither is present or valid.
+    pub fn orientation(&self) -> Option<Orientation> {
+        self.image
+            .as_ref()
+            .and_then(|image| image.orientation.clone())
+            .filter(Orientation::is_standard)
+            .or_else(|| {
+                self.video
+                    .as_ref()
+                    .and_then(|video| video.rotation)
+                    .and_then(Orientation::from_rotation)
+            })
+    }
+
+    /// The stored `(width, height)` of the image or video frame, before applying the
+    /// orientation.
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        let image = self
+            .image
+            .as_ref()
+            .and_then(|image| Some((image.image_width?, image.image_height?)));
+        image.or_else(|| {
+            let video = self.video.as_ref()?;
+            Some((video.image_width?, video.image_height?))
+        })
+    }
+
+    /// The `(width, height)` as displayed, i.e. [`ExifData::dimensions`] swapped if the
+    /// [`ExifData::orientation`] rotates by 90 or 270 degrees.
+    pub fn display_dimensions(&self) -> Option<(u32, u32)> {
+        let (width, height) = self.dimensions()?;
+        Some(match self.orientation() {
+            Some(orientation) => orientation.display_dimensions(width, height),
+            None => (width, height),
+        })
+    }
+}
+
 // --- Sub-Structs ---
 
 #[derive(Debug, Deserialize, Clone)]
@@ -52,8 +102,8 @@ pub struct AuthorMetadata {
 pub struct CameraMetadata {
     pub camera_indices: Option<u32>,
     pub cameras: Option<String>, // URI
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub circle_of_confusion: Option<String>, // String due to "mm" unit
+    #[serde(deserialize_with = "crate::parse_fn::units::optional", default)]
+    pub circle_of_confusion: Option<Millimeters>,
     #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
     pub contrast: Option<String>,
     pub depth_map_confidence_uri: Option<String>,
@@ -69,16 +119,16 @@ pub struct CameraMetadata {
     pub device_model_desc: Option<String>,
     #[serde(deserialize_with = "crate::parse_fn::undef_or_float::float", default)]
     pub digital_zoom_ratio: Option<f64>,
-    pub exposure_mode: Option<String>,
-    pub exposure_program: Option<String>,
-    pub flash: Option<String>,
+    pub exposure_mode: Option<ExposureMode>,
+    pub exposure_program: Option<ExposureProgram>,
+    pub flash: Option<Flash>,
     pub flash_energy: Option<f64>, // Assuming float, likely 0
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub focal_length: Option<String>, // String due to "mm" unit
+    #[serde(deserialize_with = "crate::parse_fn::units::optional", default)]
+    pub focal_length: Option<Millimeters>,
     #[serde(alias = "FocalLength35efl")]
     pub focal_length_35_efl: Option<String>, // Complex string format
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub focal_length_in_35mm_format: Option<String>, // String due to "mm" unit
+    #[serde(deserialize_with = "crate::parse_fn::units::optional", default)]
+    pub focal_length_in_35mm_format: Option<Millimeters>,
     #[serde(alias = "HDRPMakerNote")]
     pub hdrp_maker_note: Option<String>,
     #[serde(alias = "HdrPlusMakernote")]
@@ -101,8 +151,9 @@ pub struct CameraMetadata {
     pub lens_id: Option<String>,
     pub light_source: Option<String>,
     pub make: Option<String>,
+    #[serde(deserialize_with = "crate::parse_fn::rational::float", default)]
     pub max_aperture_value: Option<f64>,
-    pub metering_mode: Option<String>,
+    pub metering_mode: Option<MeteringMode>,
     pub model: Option<String>,
     // MotionPhoto seems boolean-like (1)
     pub motion_photo: Option<u8>, // Or Option<u8> if other values possible
@@ -133,8 +184,7 @@ pub struct CameraMetadata {
     pub trait_: Option<String>, // "Trait" is a keyword, using trait_
     #[serde(alias = "Type")]
     pub camera_type: Option<String>, // Renamed from Type to avoid conflict
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub white_balance: Option<String>, // Or String if more complex values
+    pub white_balance: Option<WhiteBalance>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -157,7 +207,9 @@ pub struct ExifToolMetadata {
 #[serde(rename_all = "PascalCase")]
 #[allow(dead_code)]
 pub struct ImageMetadata {
+    #[serde(deserialize_with = "crate::parse_fn::rational::float", default)]
     pub aperture: Option<f64>,
+    #[serde(deserialize_with = "crate::parse_fn::rational::float", default)]
     pub aperture_value: Option<f64>,
     pub bit_depth: Option<u8>,
     #[serde(deserialize_with = "crate::parse_fn::space_sep::floats", default)]
@@ -224,13 +276,17 @@ pub struct ImageMetadata {
     pub exif_image_width: Option<u32>,
     #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
     pub exif_version: Option<String>, // e.g., "0232"
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub exposure_compensation: Option<String>, // Often 0
+    #[serde(deserialize_with = "crate::parse_fn::rational::rational", default)]
+    pub exposure_compensation: Option<Rational>, // e.g., "+1/3"
     #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
     pub exposure_index: Option<String>,
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub exposure_time: Option<String>, // String to handle fractions like "1/518" or numbers like 1
-    #[serde(alias = "FNumber")]
+    #[serde(deserialize_with = "crate::parse_fn::rational::rational", default)]
+    pub exposure_time: Option<Rational>,
+    #[serde(
+        alias = "FNumber",
+        deserialize_with = "crate::parse_fn::rational::float",
+        default
+    )]
     pub f_number: Option<f64>,
     #[serde(
         alias = "FOV",
@@ -306,7 +362,7 @@ pub struct ImageMetadata {
     pub media_white_point: Option<Vec<f64>>,
     pub megapixels: Option<f64>,
     pub number_of_images: Option<u32>,
-    pub orientation: Option<String>,
+    pub orientation: Option<Orientation>,
     pub other_image_length: Option<u32>,
     pub other_image_start: Option<u32>,
     #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
@@ -335,10 +391,10 @@ pub struct ImageMetadata {
     pub rendering_intent: Option<String>,
     pub resolution_unit: Option<String>,
     pub scene_type: Option<String>,
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub shutter_speed: Option<String>, // String to handle fractions like "1/518" or numbers like 1
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub shutter_speed_value: Option<String>, // String to handle fractions like "1/100" or numbers
+    #[serde(deserialize_with = "crate::parse_fn::rational::rational", default)]
+    pub shutter_speed: Option<Rational>,
+    #[serde(deserialize_with = "crate::parse_fn::rational::rational", default)]
+    pub shutter_speed_value: Option<Rational>,
     #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
     pub software: Option<String>,
     pub source_image_height: Option<u32>,
@@ -371,15 +427,29 @@ pub struct ImageMetadata {
     pub y_resolution: Option<f64>,
 }
 
+/// GPS tags. The raw tag values are kept as strings; `latitude`, `longitude` and `altitude`
+/// hold the same position as signed decimal numbers, see [`crate::parse_fn::gps`].
 #[derive(Debug, Deserialize, Clone)]
-#[serde(rename_all = "PascalCase")]
+#[serde(rename_all = "PascalCase", remote = "Self")]
 #[allow(dead_code)]
 pub struct LocationMetadata {
-    #[serde(alias = "GPSAltitude")]
+    #[serde(
+        alias = "GPSAltitude",
+        deserialize_with = "crate::parse_fn::string::string",
+        default
+    )]
     pub gps_altitude: Option<String>, // String due to unit/ref ("m Above Sea Level")
-    #[serde(alias = "GPSAltitudeRef")]
+    #[serde(
+        alias = "GPSAltitudeRef",
+        deserialize_with = "crate::parse_fn::string::string",
+        default
+    )]
     pub gps_altitude_ref: Option<String>,
-    #[serde(alias = "GPSCoordinates")]
+    #[serde(
+        alias = "GPSCoordinates",
+        deserialize_with = "crate::parse_fn::string::string",
+        default
+    )]
     pub gps_coordinates: Option<String>, // Combined Lat/Lon string
     #[serde(alias = "GPSDOP")]
     pub gps_dop: Option<f64>, // GPS Degree of Precision
@@ -399,15 +469,27 @@ pub struct LocationMetadata {
     pub gps_img_direction: Option<f64>,
     #[serde(alias = "GPSImgDirectionRef")]
     pub gps_img_direction_ref: Option<String>,
-    #[serde(alias = "GPSLatitude")]
+    #[serde(
+        alias = "GPSLatitude",
+        deserialize_with = "crate::parse_fn::string::string",
+        default
+    )]
     pub gps_latitude: Option<String>, // String format deg ' " N/S
     #[serde(alias = "GPSLatitudeRef")]
     pub gps_latitude_ref: Option<String>,
-    #[serde(alias = "GPSLongitude")]
+    #[serde(
+        alias = "GPSLongitude",
+        deserialize_with = "crate::parse_fn::string::string",
+        default
+    )]
     pub gps_longitude: Option<String>, // String format deg ' " E/W
     #[serde(alias = "GPSLongitudeRef")]
     pub gps_longitude_ref: Option<String>,
-    #[serde(alias = "GPSPosition")]
+    #[serde(
+        alias = "GPSPosition",
+        deserialize_with = "crate::parse_fn::string::string",
+        default
+    )]
     pub gps_position: Option<String>, // Combined Lat/Lon string (often same as GPSCoordinates)
     #[serde(
         alias = "GPSProcessingMethod",
@@ -423,6 +505,69 @@ pub struct LocationMetadata {
     pub gps_time_stamp: Option<NaiveTime>,
     #[serde(alias = "GPSVersionID")]
     pub gps_version_id: Option<String>, // e.g., "2.2.0.0"
+
+    // Typed values, computed from the tags above after deserializing.
+    /// Latitude in decimal degrees, negative for south.
+    #[serde(skip)]
+    pub latitude: Option<f64>,
+    /// Longitude in decimal degrees, negative for west.
+    #[serde(skip)]
+    pub longitude: Option<f64>,
+    /// Altitude in meters, negative below sea level.
+    #[serde(skip)]
+    pub altitude: Option<f64>,
+}
+
+impl<'de> Deserialize<'de> for LocationMetadata {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // The derived (remote) implementation reads the raw tags.
+        let mut location = LocationMetadata::deserialize(deserializer)?;
+        location.fill_typed();
+        Ok(location)
+    }
+}
+
+impl LocationMetadata {
+    /// The position as a [`GpsCoordinate`], if latitude and longitude are known.
+    pub fn coordinate(&self) -> Option<GpsCoordinate> {
+        Some(GpsCoordinate {
+            lat: self.latitude?,
+            lon: self.longitude?,
+            alt: self.altitude,
+        })
+    }
+
+    /// Computes the typed fields from the separate latitude/longitude/altitude tags and their
+    /// `Ref` tags, falling back to the combined `GPSPosition` or `GPSCoordinates`.
+    fn fill_typed(&mut self) {
+        let position = self
+            .gps_position
+            .as_deref()
+            .or(self.gps_coordinates.as_deref())
+            .and_then(gps::parse_position);
+
+        self.latitude = self
+            .gps_latitude
+            .as_deref()
+            .and_then(gps::parse_dms)
+            .map(|lat| gps::apply_ref(lat, self.gps_latitude_ref.as_deref()))
+            .or(position.map(|p| p.lat));
+        self.longitude = self
+            .gps_longitude
+            .as_deref()
+            .and_then(gps::parse_dms)
+            .map(|lon| gps::apply_ref(lon, self.gps_longitude_ref.as_deref()))
+            .or(position.map(|p| p.lon));
+        self.altitude = self
+            .gps_altitude
+            .as_deref()
+            .and_then(gps::parse_altitude)
+            .map(|alt| gps::apply_ref(alt, self.gps_altitude_ref.as_deref()))
+            .or(position.and_then(|p| p.alt));
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -443,8 +588,9 @@ pub struct OtherMetadata {
     pub envelope_record_version: Option<u32>,
     pub file_name: Option<String>,
     pub file_permissions: Option<String>, // e.g., "-rw-rw-rw-"
-    pub file_size: Option<String>,        // String due to unit "kB", "MB"
-    pub file_type: Option<String>,        // e.g., "JPEG", "MP4"
+    #[serde(deserialize_with = "crate::parse_fn::units::optional", default)]
+    pub file_size: Option<Bytes>,
+    pub file_type: Option<String>,           // e.g., "JPEG", "MP4"
     pub file_type_extension: Option<String>, // e.g., "jpg", "mp4"
     #[serde(alias = "FilterId")]
     pub filter_id: Option<u32>,
@@ -634,8 +780,8 @@ pub struct UnknownMetadata {
 #[serde(rename_all = "PascalCase")]
 #[allow(dead_code)]
 pub struct VideoMetadata {
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub avg_bitrate: Option<String>, // String due to unit "Mbps"
+    #[serde(deserialize_with = "crate::parse_fn::units::optional", default)]
+    pub avg_bitrate: Option<Bitrate>,
     pub color_primaries: Option<String>,
     pub color_profiles: Option<String>, // e.g. "nclx"
     #[serde(
@@ -643,11 +789,11 @@ pub struct VideoMetadata {
         default
     )]
     pub compatible_brands: Option<Vec<String>>, // e.g. ["isom", "mp42"]
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub current_time: Option<String>, // String due to unit "s"
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub duration: Option<String>, // String due to unit "s" or format "0:02:26"
-    pub graphics_mode: Option<String>,  // e.g., "srcCopy"
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub current_time: Option<Duration>,
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub duration: Option<Duration>,
+    pub graphics_mode: Option<String>,       // e.g., "srcCopy"
     pub handler_description: Option<String>, // e.g. "SoundHandle"
     pub handler_type: Option<String>,
     pub image_height: Option<u32>,
@@ -657,8 +803,8 @@ pub struct VideoMetadata {
     pub matrix_structure: Option<String>, // e.g., "1 0 0 0 1 0 0 0 1"
     pub media_data_offset: Option<u64>,
     pub media_data_size: Option<u64>,
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub media_duration: Option<String>, // String like Duration
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub media_duration: Option<Duration>,
     pub media_header_version: Option<u32>,
     pub media_time_scale: Option<u32>,
     pub minor_version: Option<String>, // e.g., "0.0.0"
@@ -668,31 +814,73 @@ pub struct VideoMetadata {
     pub next_track_id: Option<u32>,
     #[serde(alias = "OpColor")]
     pub op_color: Option<String>, // e.g., "0 0 0"
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub poster_time: Option<String>, // String due to unit "s"
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub poster_time: Option<Duration>,
     pub preferred_rate: Option<f64>, // Often 1.0 or 1
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub preferred_volume: Option<String>, // String due to "%"
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub preview_duration: Option<String>, // String due to unit "s"
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub preview_time: Option<String>, // String due to unit "s"
-    pub rotation: Option<i32>,       // e.g., 0, 90, 270
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub selection_duration: Option<String>, // String due to unit "s"
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub selection_time: Option<String>, // String due to unit "s"
+    #[serde(deserialize_with = "crate::parse_fn::units::optional", default)]
+    pub preferred_volume: Option<Percent>,
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub preview_duration: Option<Duration>,
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub preview_time: Option<Duration>,
+    pub rotation: Option<i32>, // e.g., 0, 90, 270
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub selection_duration: Option<Duration>,
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub selection_time: Option<Duration>,
     pub time_scale: Option<u32>,
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub track_duration: Option<String>, // String like Duration
+    #[serde(deserialize_with = "crate::parse_fn::units::duration", default)]
+    pub track_duration: Option<Duration>,
     pub track_header_version: Option<u32>,
     #[serde(alias = "TrackID")]
     pub track_id: Option<u32>,
     pub track_layer: Option<i32>, // Can be negative?
-    #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
-    pub track_volume: Option<String>, // String due to "%"
+    #[serde(deserialize_with = "crate::parse_fn::units::optional", default)]
+    pub track_volume: Option<Percent>,
     pub transfer_characteristics: Option<String>,
     pub video_frame_rate: Option<f64>,
     #[serde(deserialize_with = "crate::parse_fn::string::string", default)]
     pub video_full_range_flag: Option<String>, // Full, Limited
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_location_metadata() {
+        let location: LocationMetadata = serde_json::from_value(json!({
+            "GPSLatitude": "33 deg 51' 36.00\"",
+            "GPSLatitudeRef": "South",
+            "GPSLongitude": "151 deg 12' 36.00\" E",
+            "GPSAltitude": "5 m",
+            "GPSAltitudeRef": "Below Sea Level",
+        }))
+        .unwrap();
+        let coordinate = location.coordinate().unwrap();
+        assert_close(coordinate.lat, -33.86);
+        assert_close(coordinate.lon, 151.21);
+        assert_close(coordinate.alt.unwrap(), -5.0);
+
+        // `-n` output, with only the combined position.
+        let location: LocationMetadata = serde_json::from_value(json!({
+            "GPSPosition": "52.37 4.89",
+            "GPSAltitudeRef": 0,
+        }))
+        .unwrap();
+        assert_eq!(location.latitude, Some(52.37));
+        assert_eq!(location.longitude, Some(4.89));
+        assert_eq!(location.altitude, None);
+        assert_eq!(location.gps_altitude_ref.as_deref(), Some("0"));
+    }
+}
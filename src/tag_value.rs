@@ -0,0 +1,165 @@
+use crate::error::ExifToolError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// A tag's machine-readable value together with its print-converted form, as read by
+/// [`ExifTool::read_tag_values`](crate::ExifTool::read_tag_values).
+///
+/// For `Orientation`, `raw` is `6` and `printed` is `Some("Rotate 90 CW")`. `printed` is
+/// `None` when `exiftool` applies no print conversion, e.g. for `Make`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagValue {
+    /// The value as returned with `-n`.
+    pub raw: Value,
+    /// The print-converted value, if it differs from `raw`.
+    pub printed: Option<String>,
+}
+
+/// Which form of a tag's value [`ExifTool::read_tag_as`](crate::ExifTool::read_tag_as) reads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Representation {
+    /// The machine-readable value, as with `-n` (e.g. `6`).
+    Raw,
+    /// The print-converted value `exiftool` returns by default (e.g. `"Rotate 90 CW"`).
+    #[default]
+    Printed,
+}
+
+impl TagValue {
+    /// Deserializes the raw value into `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`serde_json::Error`] if the value doesn't match `T`.
+    pub fn raw_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(&self.raw)
+    }
+
+    /// Parses one tag of `exiftool -json -l` output, which looks like
+    /// `{"desc": "Orientation", "id": 274, "val": "Rotate 90 CW", "num": 6}`. `num` is only
+    /// present if print conversion changed the value.
+    fn from_long(value: Value) -> Self {
+        let Value::Object(mut object) = value else {
+            return TagValue {
+                raw: value,
+                printed: None,
+            };
+        };
+        let val = object.remove("val").unwrap_or(Value::Null);
+        match object.remove("num") {
+            Some(num) => TagValue {
+                raw: num,
+                printed: Some(match val {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                }),
+            },
+            None => TagValue {
+                raw: val,
+                printed: None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for TagValue {
+    /// Writes the printed value, or the raw value if there is none. Strings are written
+    /// without quotes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.printed, &self.raw) {
+            (Some(printed), _) => f.write_str(printed),
+            (None, Value::String(raw)) => f.write_str(raw),
+            (None, raw) => write!(f, "{}", raw),
+        }
+    }
+}
+
+/// The arguments to read `tags` (or all tags if empty) with both values.
+pub(crate) fn tag_value_args(tags: &[&str]) -> Vec<String> {
+    std::iter::once("-l".to_string())
+        .chain(tags.iter().map(|t| format!("-{}", t)))
+        .collect()
+}
+
+/// Converts a file's `exiftool -json -l` object into a [`TagValue`] per tag, without
+/// `SourceFile`.
+pub(crate) fn parse_long_json(value: Value) -> BTreeMap<String, TagValue> {
+    let Value::Object(object) = value else {
+        return BTreeMap::new();
+    };
+    object
+        .into_iter()
+        .filter(|(tag, _)| tag != "SourceFile")
+        .map(|(tag, value)| (tag, TagValue::from_long(value)))
+        .collect()
+}
+
+/// Deserializes the result of looking up a single tag into `T`. A missing tag becomes `None`
+/// if `T` is an `Option`, otherwise it stays [`ExifToolError::TagNotFound`].
+pub(crate) fn deserialize_tag<T: DeserializeOwned>(
+    file_path: &Path,
+    tag: &str,
+    value: Result<Value, ExifToolError>,
+) -> Result<T, ExifToolError> {
+    match value {
+        Ok(value) => serde_json::from_value(value).map_err(|e| ExifToolError::TagDeserialization {
+            path: file_path.to_path_buf(),
+            tag: tag.to_string(),
+            error: e,
+        }),
+        // Deserializing `null` only succeeds if T is Option-like.
+        Err(ExifToolError::TagNotFound { path, tag }) => serde_json::from_value(Value::Null)
+            .map_err(|_| ExifToolError::TagNotFound { path, tag }),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_long_json() {
+        let values = parse_long_json(json!({
+            "SourceFile": "image.jpg",
+            "Make": {"id": 271, "desc": "Make", "val": "Huawei"},
+            "Orientation": {"id": 274, "desc": "Orientation", "val": "Rotate 90 CW", "num": 6},
+            "FNumber": {"id": 33437, "desc": "F Number", "val": 1.8, "num": 1.79999995231628},
+        }));
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            values["Make"],
+            TagValue {
+                raw: json!("Huawei"),
+                printed: None
+            }
+        );
+        let orientation = &values["Orientation"];
+        assert_eq!(orientation.raw_as::<u16>().unwrap(), 6);
+        assert_eq!(orientation.printed.as_deref(), Some("Rotate 90 CW"));
+        assert_eq!(orientation.to_string(), "Rotate 90 CW");
+        assert_eq!(values["FNumber"].printed.as_deref(), Some("1.8"));
+        assert_eq!(values["Make"].to_string(), "Huawei");
+    }
+
+    #[test]
+    fn test_deserialize_tag_missing() {
+        let missing = || {
+            Err(ExifToolError::TagNotFound {
+                path: "image.jpg".into(),
+                tag: "Orientation".to_string(),
+            })
+        };
+        let optional: Option<u16> =
+            deserialize_tag(Path::new("image.jpg"), "Orientation", missing()).unwrap();
+        assert_eq!(optional, None);
+        let required: Result<u16, _> =
+            deserialize_tag(Path::new("image.jpg"), "Orientation", missing());
+        assert!(matches!(required, Err(ExifToolError::TagNotFound { .. })));
+    }
+}
@@ -0,0 +1,172 @@
+use serde_json::Value;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A warning reported by `exiftool` while processing a command.
+///
+/// Warnings don't fail a command, but often point at damaged or unusual metadata,
+/// e.g. `Bad MakerNotes offset`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Warning {
+    /// The warning text, without the `Warning:` and `[minor]` prefixes or the file name.
+    pub message: String,
+    /// The file the warning relates to, if `exiftool` named one.
+    pub file: Option<PathBuf>,
+    /// Whether this is a minor warning (`[minor]`). Minor warnings can be ignored with
+    /// the `-m` option.
+    pub minor: bool,
+}
+
+impl Warning {
+    /// Parses a `Warning: [minor] message - file` stderr line.
+    ///
+    /// `files` are the file arguments of the command; the file suffix is only split off when
+    /// it matches one of them, since messages themselves may contain ` - `.
+    pub(crate) fn from_stderr_line(line: &str, files: &[&str]) -> Option<Self> {
+        let rest = line.strip_prefix("Warning: ")?;
+        let (message, minor) = strip_minor(rest);
+        let (message, file) = match files
+            .iter()
+            .find_map(|f| Some((message.strip_suffix(f)?.strip_suffix(" - ")?, *f)))
+        {
+            Some((message, file)) => (message, Some(PathBuf::from(file))),
+            None => (message, None),
+        };
+        Some(Self {
+            message: message.to_string(),
+            file,
+            minor,
+        })
+    }
+
+    /// Extracts the `Warning` tag(s) from one file's `-json` output object.
+    pub(crate) fn from_json_object(object: &Value) -> Vec<Self> {
+        let file = object
+            .get("SourceFile")
+            .and_then(Value::as_str)
+            .map(PathBuf::from);
+        let messages: Vec<&str> = match object.get("Warning") {
+            Some(Value::String(message)) => vec![message],
+            Some(Value::Array(messages)) => messages.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        messages
+            .into_iter()
+            .map(|message| {
+                let (message, minor) = strip_minor(message);
+                Self {
+                    message: message.to_string(),
+                    file: file.clone(),
+                    minor,
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Warning: ")?;
+        if self.minor {
+            write!(f, "[minor] ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(file) = &self.file {
+            write!(f, " - {}", file.display())?;
+        }
+        Ok(())
+    }
+}
+
+fn strip_minor(message: &str) -> (&str, bool) {
+    match message.strip_prefix("[minor] ") {
+        Some(message) => (message, true),
+        None => (message, false),
+    }
+}
+
+/// A command's result together with the warnings `exiftool` reported while producing it.
+///
+/// Returned by [`ExifTool::execute_with_diagnostics`](crate::ExifTool::execute_with_diagnostics)
+/// and [`ExifTool::json_batch_with_diagnostics`](crate::ExifTool::json_batch_with_diagnostics).
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithWarnings<T> {
+    /// The command's result, e.g. its stdout or parsed JSON.
+    pub value: T,
+    /// The warnings from the command's stderr, in the order `exiftool` printed them.
+    pub warnings: Vec<Warning>,
+}
+
+/// Parses all warning lines in a command's stderr. `args` is the full command.
+pub(crate) fn stderr_warnings(args: &[&str], stderr_lines: &[String]) -> Vec<Warning> {
+    let files: Vec<&str> = args
+        .iter()
+        .copied()
+        .filter(|a| !a.starts_with('-'))
+        .collect();
+    stderr_lines
+        .iter()
+        .filter_map(|line| Warning::from_stderr_line(line, &files))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_stderr_line() {
+        let files = ["data/my - photo.jpg", "b.jpg"];
+        let warning =
+            Warning::from_stderr_line("Warning: [minor] Bad MakerNotes offset - b.jpg", &files);
+        assert_eq!(
+            warning,
+            Some(Warning {
+                message: "Bad MakerNotes offset".to_string(),
+                file: Some(PathBuf::from("b.jpg")),
+                minor: true,
+            })
+        );
+
+        let warning =
+            Warning::from_stderr_line("Warning: Invalid date/time - data/my - photo.jpg", &files)
+                .unwrap();
+        assert_eq!(warning.message, "Invalid date/time");
+        assert_eq!(warning.file, Some(PathBuf::from("data/my - photo.jpg")));
+        assert!(!warning.minor);
+
+        let warning = Warning::from_stderr_line("Warning: No writable tags set", &files).unwrap();
+        assert_eq!(warning.message, "No writable tags set");
+        assert_eq!(warning.file, None);
+
+        assert_eq!(
+            Warning::from_stderr_line("Error: File not found - b.jpg", &files),
+            None
+        );
+    }
+
+    #[test]
+    fn test_from_json_object() {
+        let object = json!({
+            "SourceFile": "a.jpg",
+            "Warning": "[minor] Bad MakerNotes offset",
+        });
+        assert_eq!(
+            Warning::from_json_object(&object),
+            vec![Warning {
+                message: "Bad MakerNotes offset".to_string(),
+                file: Some(PathBuf::from("a.jpg")),
+                minor: true,
+            }]
+        );
+        assert!(Warning::from_json_object(&json!({"SourceFile": "a.jpg"})).is_empty());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let line = "Warning: [minor] Bad MakerNotes offset - b.jpg";
+        let warning = Warning::from_stderr_line(line, &["b.jpg"]).unwrap();
+        assert_eq!(warning.to_string(), line);
+    }
+}
@@ -0,0 +1,1130 @@
+use crate::error::ExifToolError;
+use crate::exiftool::{check_stderr, CommandOutput};
+use crate::warning::{stderr_warnings, Warning};
+use log::warn;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+
+/// A set of tag assignments that are applied together in a single `exiftool` command.
+///
+/// Every file is rewritten once with all assignments, instead of once per tag as with
+/// repeated [`ExifTool::write_tag`](crate::ExifTool::write_tag) calls. If the command fails
+/// for a file, none of the assignments are written to it.
+///
+/// Tag names may be group-qualified (e.g. `"XMP-dc:Title"`, `"IPTC:Keywords"`) to target a
+/// specific group. Apply the set with [`ExifTool::write_tags`](crate::ExifTool::write_tags).
+///
+/// # Examples
+///
+/// ```no_run
+/// use exiftool::{ExifTool, ExifToolError, WriteSet};
+/// use std::path::Path;
+///
+/// # fn main() -> Result<(), ExifToolError> {
+/// let mut et = ExifTool::new()?;
+/// let writes = WriteSet::new()
+///     .set("XMP:Title", "Sunset")
+///     .set("Artist", "Jane Doe")
+///     .append("Keywords", "beach")
+///     .remove("Keywords", "draft")
+///     .delete("Comment")
+///     .copy("XMP:CreateDate", "EXIF:DateTimeOriginal");
+///
+/// let report = et.write_tags([Path::new("image.jpg")], &writes, &["-overwrite_original"])?;
+/// println!("{} updated, {} unchanged", report.updated, report.unchanged);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteSet {
+    assignments: Vec<Assignment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Assignment {
+    Set { tag: String, value: String },
+    Append { tag: String, value: String },
+    Remove { tag: String, value: String },
+    Delete { tag: String },
+    Copy { tag: String, source: String },
+}
+
+impl WriteSet {
+    /// Creates an empty write set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a tag to a value (`-TAG=VALUE`). For list tags like `Keywords`, this replaces
+    /// the list when the tag is set for the first time in the set, and adds to it afterwards.
+    pub fn set(mut self, tag: impl Into<String>, value: impl ToString) -> Self {
+        self.assignments.push(Assignment::Set {
+            tag: tag.into(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// Adds an item to a list tag, keeping the existing items (`-TAG+=VALUE`).
+    /// For numeric tags this adds to the value instead, e.g. shifting a date.
+    pub fn append(mut self, tag: impl Into<String>, value: impl ToString) -> Self {
+        self.assignments.push(Assignment::Append {
+            tag: tag.into(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// Removes an item from a list tag (`-TAG-=VALUE`).
+    pub fn remove(mut self, tag: impl Into<String>, value: impl ToString) -> Self {
+        self.assignments.push(Assignment::Remove {
+            tag: tag.into(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// Deletes a tag (`-TAG=`). Use `"GROUP:all"` to delete a whole group, e.g. `"GPS:all"`.
+    pub fn delete(mut self, tag: impl Into<String>) -> Self {
+        self.assignments
+            .push(Assignment::Delete { tag: tag.into() });
+        self
+    }
+
+    /// Copies the value of another tag of the same file into `tag` (`-TAG<SOURCE`).
+    ///
+    /// The source value is read from the file as it was before the command, so assignments
+    /// earlier in the set don't affect it: copying a tag that is also `set` copies its old
+    /// value.
+    pub fn copy(mut self, tag: impl Into<String>, source: impl Into<String>) -> Self {
+        self.assignments.push(Assignment::Copy {
+            tag: tag.into(),
+            source: source.into(),
+        });
+        self
+    }
+
+    /// Returns `true` if the set contains no assignments.
+    pub fn is_empty(&self) -> bool {
+        self.assignments.is_empty()
+    }
+
+    /// The number of assignments in the set.
+    pub fn len(&self) -> usize {
+        self.assignments.len()
+    }
+
+    /// Renders the assignments to `exiftool` arguments, in the order they were added.
+    pub fn to_args(&self) -> Vec<String> {
+        self.assignments
+            .iter()
+            .map(|assignment| match assignment {
+                Assignment::Set { tag, value } => format!("-{}={}", tag, value),
+                Assignment::Append { tag, value } => format!("-{}+={}", tag, value),
+                Assignment::Remove { tag, value } => format!("-{}-={}", tag, value),
+                Assignment::Delete { tag } => format!("-{}=", tag),
+                Assignment::Copy { tag, source } => format!("-{}<{}", tag, source),
+            })
+            .collect()
+    }
+
+    /// The arguments that read the current value of every tag this set touches in one
+    /// `-json` call, including all tags of deleted groups like `GPS:all`.
+    ///
+    /// Keys are group-qualified with both group families (`-G0:1`, e.g.
+    /// `EXIF:IFD0:Artist`), so `EXIF:Artist` and `XMP-dc:Artist` can be told apart, and
+    /// duplicates are kept (`-a`). Returns no arguments if the set is empty.
+    pub(crate) fn read_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = Vec::new();
+        let mut push = |tag: &str| {
+            let arg = format!("-{}", tag);
+            if !args.contains(&arg) {
+                args.push(arg);
+            }
+        };
+        for assignment in &self.assignments {
+            match assignment {
+                Assignment::Copy { tag, source } => {
+                    push(tag);
+                    push(source);
+                }
+                Assignment::Set { tag, .. }
+                | Assignment::Append { tag, .. }
+                | Assignment::Remove { tag, .. }
+                | Assignment::Delete { tag } => push(tag),
+            }
+        }
+        if !args.is_empty() {
+            args.splice(0..0, ["-a".to_string(), "-G0:1".to_string()]);
+        }
+        args
+    }
+
+    /// Simulates the assignments against a file's current metadata and returns the tags
+    /// whose value would change.
+    ///
+    /// `current` is the file's `-json` output for [`WriteSet::read_args`]. Changes are
+    /// tracked per tag as named in the set: `EXIF:Artist` and `XMP:Artist` are separate
+    /// changes, each starting from the value of its own group.
+    pub(crate) fn diff(&self, current: &Map<String, Value>) -> Vec<TagChange> {
+        // Changes in the order tags are first touched.
+        let mut changes: Vec<PendingChange> = Vec::new();
+        fn entry<'a>(
+            changes: &'a mut Vec<PendingChange>,
+            current: &Map<String, Value>,
+            tag: &str,
+        ) -> &'a mut PendingChange {
+            let index = match changes.iter().position(|pending| pending.is_for(tag)) {
+                Some(index) => index,
+                None => {
+                    let (key, old) = match lookup(current, tag) {
+                        Some((key, old)) => (Some(key.clone()), Some(old.clone())),
+                        None => (None, None),
+                    };
+                    changes.push(PendingChange {
+                        change: TagChange {
+                            tag: tag.to_string(),
+                            new: old.clone(),
+                            old,
+                        },
+                        key,
+                        from_group: false,
+                        replaced: false,
+                    });
+                    changes.len() - 1
+                }
+            };
+            &mut changes[index]
+        }
+
+        for assignment in &self.assignments {
+            match assignment {
+                Assignment::Set { tag, value } => {
+                    let pending = entry(&mut changes, current, tag);
+                    pending.change.new = if pending.replaced {
+                        Some(list_with(pending.change.new.take(), value))
+                    } else {
+                        Some(Value::String(value.clone()))
+                    };
+                    pending.replaced = true;
+                }
+                Assignment::Append { tag, value } => {
+                    let pending = entry(&mut changes, current, tag);
+                    pending.change.new = Some(list_with(pending.change.new.take(), value));
+                }
+                Assignment::Remove { tag, value } => {
+                    let pending = entry(&mut changes, current, tag);
+                    pending.change.new = list_without(pending.change.new.take(), value);
+                }
+                Assignment::Delete { tag } if is_group_wildcard(tag) => {
+                    // Earlier assignments to tags of the group are undone...
+                    for pending in changes.iter_mut().filter(|p| p.in_group(tag)) {
+                        pending.change.new = None;
+                        pending.replaced = false;
+                    }
+                    // ...and the group's other tags are removed.
+                    for (key, old) in current {
+                        let tracked = changes.iter().any(|p| p.key.as_ref() == Some(key));
+                        if in_group(key, tag) && is_deletable(key) && !tracked {
+                            changes.push(PendingChange {
+                                change: TagChange {
+                                    tag: display_name(key),
+                                    old: Some(old.clone()),
+                                    new: None,
+                                },
+                                key: Some(key.clone()),
+                                from_group: true,
+                                replaced: false,
+                            });
+                        }
+                    }
+                }
+                Assignment::Delete { tag } => {
+                    let pending = entry(&mut changes, current, tag);
+                    pending.change.new = None;
+                    pending.replaced = false;
+                }
+                Assignment::Copy { tag, source } => {
+                    let value = lookup(current, source).map(|(_, value)| value.clone());
+                    let pending = entry(&mut changes, current, tag);
+                    pending.change.new = value;
+                    pending.replaced = true;
+                }
+            }
+        }
+
+        changes
+            .into_iter()
+            .map(|pending| pending.change)
+            .filter(|change| as_strings(&change.old) != as_strings(&change.new))
+            .collect()
+    }
+}
+
+/// A [`TagChange`] while [`WriteSet::diff`] applies the assignments.
+struct PendingChange {
+    change: TagChange,
+    /// The `-G0:1` key the old value was read from, if the file has the tag.
+    key: Option<String>,
+    /// Whether a group deletion created the entry. Later assignments to any tag name
+    /// matching `key` update it instead of starting from the file's value.
+    from_group: bool,
+    /// Whether a `=` assignment already replaced the value (later `=` assignments of list
+    /// tags add to it).
+    replaced: bool,
+}
+
+impl PendingChange {
+    fn is_for(&self, tag: &str) -> bool {
+        self.change.tag.eq_ignore_ascii_case(tag)
+            || (self.from_group && self.key.as_deref().is_some_and(|key| key_matches(key, tag)))
+    }
+
+    /// Whether deleting `wildcard` (`all` or `GROUP:all`) removes this tag.
+    fn in_group(&self, wildcard: &str) -> bool {
+        match &self.key {
+            Some(key) => in_group(key, wildcard) && is_deletable(key),
+            None => match (wildcard.rsplit_once(':'), self.change.tag.rsplit_once(':')) {
+                (None, _) => true,
+                (Some((group, _)), Some((tag_group, _))) => group.eq_ignore_ascii_case(tag_group),
+                (Some(_), None) => false,
+            },
+        }
+    }
+}
+
+/// Selects which tags [`ExifTool::copy_tags`](crate::ExifTool::copy_tags) copies from the
+/// source file (`-tagsFromFile`).
+///
+/// [`CopySelection::all`] (the default) copies every writable tag. Adding tags or redirects
+/// copies only those, and excluded tags are skipped in either case.
+///
+/// # Examples
+///
+/// ```
+/// use exiftool::CopySelection;
+///
+/// // Only GPS tags and the capture date, but no GPS altitude.
+/// let selection = CopySelection::all()
+///     .tag("GPS:all")
+///     .tag("DateTimeOriginal")
+///     .exclude("GPSAltitude");
+/// assert_eq!(
+///     selection.to_args(),
+///     ["-GPS:all", "-DateTimeOriginal", "--GPSAltitude"]
+/// );
+///
+/// // Everything, written to XMP instead of EXIF, but without maker notes.
+/// let selection = CopySelection::all()
+///     .redirect("XMP:all", "EXIF:all")
+///     .exclude("MakerNotes:all");
+/// assert_eq!(selection.to_args(), ["-XMP:all<EXIF:all", "--MakerNotes:all"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopySelection {
+    tags: Vec<String>,
+    excluded_tags: Vec<String>,
+}
+
+impl CopySelection {
+    /// Selects all writable tags of the source file.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Copies a tag or group to the same tag of the destination (`-TAG`), e.g. `"Artist"`
+    /// or `"GPS:all"`.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(format!("-{}", tag.into()));
+        self
+    }
+
+    /// Copies several tags, see [`CopySelection::tag`].
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags
+            .extend(tags.into_iter().map(|t| format!("-{}", t.into())));
+        self
+    }
+
+    /// Copies the `source` tag of the source file to a different `target` tag
+    /// (`-TARGET<SOURCE`), e.g. `("XMP:all", "EXIF:all")` to write all EXIF tags as XMP.
+    pub fn redirect(mut self, target: impl Into<String>, source: impl Into<String>) -> Self {
+        self.tags
+            .push(format!("-{}<{}", target.into(), source.into()));
+        self
+    }
+
+    /// Skips a tag or group (`--TAG`), e.g. `"MakerNotes:all"`.
+    pub fn exclude(mut self, tag: impl Into<String>) -> Self {
+        self.excluded_tags.push(tag.into());
+        self
+    }
+
+    /// Renders the selection to the `exiftool` arguments following `-tagsFromFile SRCFILE`.
+    pub fn to_args(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .cloned()
+            .chain(self.excluded_tags.iter().map(|t| format!("--{}", t)))
+            .collect()
+    }
+}
+
+/// A change a write would make to a single tag, as computed by
+/// [`ExifTool::write_tags_dry_run`](crate::ExifTool::write_tags_dry_run).
+///
+/// Values are in the same (print-converted) form as [`ExifTool::json`](crate::ExifTool::json)
+/// returns them. `None` means the tag doesn't exist (`old`) or is deleted (`new`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagChange {
+    /// The tag as named in the [`WriteSet`], or `Group:Tag` with the specific (family 1)
+    /// group, e.g. `GPS:GPSLatitude`, for tags removed by a group deletion like `GPS:all`.
+    pub tag: String,
+    /// The current value, or `None` if the file doesn't have the tag.
+    pub old: Option<Value>,
+    /// The value after the write, or `None` if the tag is deleted.
+    pub new: Option<Value>,
+}
+
+/// The dry-run result for one file: its path and the changes a write would make to it.
+///
+/// Returned by [`ExifTool::write_tags_dry_run`](crate::ExifTool::write_tags_dry_run).
+pub type FileChanges = (PathBuf, Result<Vec<TagChange>, ExifToolError>);
+
+fn is_group_wildcard(tag: &str) -> bool {
+    tag.eq_ignore_ascii_case("all")
+        || tag
+            .rsplit_once(':')
+            .is_some_and(|(_, name)| name.eq_ignore_ascii_case("all"))
+}
+
+/// Splits a `-G0:1` key like `EXIF:IFD0:Artist` into its groups and the tag name.
+fn split_key(key: &str) -> (Vec<&str>, &str) {
+    match key.rsplit_once(':') {
+        Some((groups, name)) => (groups.split(':').collect(), name),
+        None => (Vec::new(), key),
+    }
+}
+
+/// Whether a `-G0:1` key belongs to `group` in either family, e.g. `EXIF:IFD0:Artist` to
+/// `EXIF` and `IFD0`.
+fn has_group(key: &str, group: &str) -> bool {
+    split_key(key)
+        .0
+        .iter()
+        .any(|g| g.eq_ignore_ascii_case(group))
+}
+
+/// Whether a `-G0:1` key is removed by deleting `wildcard` (`all` or `GROUP:all`).
+fn in_group(key: &str, wildcard: &str) -> bool {
+    match wildcard.rsplit_once(':') {
+        Some((group, _)) => has_group(key, group),
+        None => true,
+    }
+}
+
+/// Tags of these groups are reported by `-all`, but never deleted by `-all=`.
+fn is_deletable(key: &str) -> bool {
+    let (groups, _) = split_key(key);
+    groups
+        .first()
+        .is_some_and(|group| !["ExifTool", "File", "System", "Composite"].contains(group))
+}
+
+/// A `-G0:1` key as `Group:Tag` with the specific (family 1) group, e.g. `IFD0:Artist`.
+fn display_name(key: &str) -> String {
+    match split_key(key) {
+        (groups, name) if !groups.is_empty() => format!("{}:{}", groups[groups.len() - 1], name),
+        _ => key.to_string(),
+    }
+}
+
+/// Whether a `-G0:1` key is the tag as named in a [`WriteSet`]. A group-qualified tag only
+/// matches keys of that group, an unqualified one any key with that name.
+fn key_matches(key: &str, tag: &str) -> bool {
+    let (group, name) = match tag.rsplit_once(':') {
+        Some((group, name)) => (Some(group), name),
+        None => (None, tag),
+    };
+    split_key(key).1.eq_ignore_ascii_case(name) && group.is_none_or(|group| has_group(key, group))
+}
+
+/// The `-G0:1` key and current value of a tag as named in a [`WriteSet`], see
+/// [`key_matches`]. An unqualified tag returns the first key with that name.
+fn lookup<'a>(map: &'a Map<String, Value>, tag: &str) -> Option<(&'a String, &'a Value)> {
+    map.iter().find(|(key, _)| key_matches(key, tag))
+}
+
+fn list_with(value: Option<Value>, item: &str) -> Value {
+    let mut items = match value {
+        None => Vec::new(),
+        Some(Value::Array(items)) => items,
+        Some(value) => vec![value],
+    };
+    items.push(Value::String(item.to_string()));
+    Value::Array(items)
+}
+
+fn list_without(value: Option<Value>, item: &str) -> Option<Value> {
+    let items: Vec<Value> = match value? {
+        Value::Array(items) => items,
+        value => vec![value],
+    }
+    .into_iter()
+    .filter(|v| value_to_string(v) != item)
+    .collect();
+    match items.len() {
+        0 => None,
+        1 => items.into_iter().next(),
+        _ => Some(Value::Array(items)),
+    }
+}
+
+/// Normalizes a value for comparison, so `"1"`, `1` and `["1"]` are equal.
+fn as_strings(value: &Option<Value>) -> Vec<String> {
+    match value {
+        None => Vec::new(),
+        Some(Value::Array(items)) => items.iter().map(value_to_string).collect(),
+        Some(value) => vec![value_to_string(value)],
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// What happens to the original file when a tag is written.
+///
+/// Set with [`ExifToolBuilder::backup_policy`](crate::ExifToolBuilder::backup_policy) or
+/// [`ExifTool::set_backup_policy`](crate::ExifTool::set_backup_policy) and applied to every
+/// write method that modifies files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BackupPolicy {
+    /// Keep a copy of the original file as `{filename}_original` (`exiftool`'s default).
+    /// See [`ExifTool::restore_original`](crate::ExifTool::restore_original) and
+    /// [`ExifTool::delete_original`](crate::ExifTool::delete_original).
+    #[default]
+    KeepOriginal,
+    /// Don't keep a backup and rewrite the original file in place, so its inode, creation
+    /// date and other filesystem attributes are preserved (`-overwrite_original_in_place`).
+    /// Slower than [`BackupPolicy::OverwriteOriginal`].
+    OverwriteInPlace,
+    /// Don't keep a backup; the original file is replaced by the rewritten one
+    /// (`-overwrite_original`).
+    OverwriteOriginal,
+}
+
+impl BackupPolicy {
+    /// The `exiftool` option for this policy, if any.
+    pub(crate) fn arg(self) -> Option<&'static str> {
+        match self {
+            BackupPolicy::KeepOriginal => None,
+            BackupPolicy::OverwriteInPlace => Some("-overwrite_original_in_place"),
+            BackupPolicy::OverwriteOriginal => Some("-overwrite_original"),
+        }
+    }
+}
+
+/// Which metadata [`ExifTool::strip_metadata`](crate::ExifTool::strip_metadata) removes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StripPolicy {
+    /// Removes all writable metadata (`-all=`).
+    All,
+    /// Removes all writable metadata, but keeps the ICC color profile so colors still render
+    /// correctly (`-all= -tagsFromFile @ -ICC_Profile`).
+    KeepColorProfile,
+    /// Removes only data that identifies a place or person: GPS tags (in any group), serial
+    /// numbers, owner names and maker notes.
+    PrivacyOnly,
+    /// Removes the given groups (`-GROUP:all=`), e.g. `["XMP", "IPTC"]`. Entries containing a
+    /// `:` or `*` are used as tag names instead, e.g. `"XMP-exif:GPS*"`.
+    Custom(Vec<String>),
+}
+
+impl StripPolicy {
+    /// Renders the policy to `exiftool` arguments.
+    pub fn to_args(&self) -> Vec<String> {
+        let args: &[&str] = match self {
+            StripPolicy::All => &["-all="],
+            StripPolicy::KeepColorProfile => &["-all=", "-tagsFromFile", "@", "-ICC_Profile"],
+            StripPolicy::PrivacyOnly => &[
+                "-GPS:all=",
+                "-GPS*=",
+                "-*SerialNumber=",
+                "-*OwnerName=",
+                "-MakerNotes:all=",
+            ],
+            StripPolicy::Custom(groups) => {
+                return groups
+                    .iter()
+                    .map(|group| {
+                        if group.contains(':') || group.contains('*') {
+                            format!("-{}=", group)
+                        } else {
+                            format!("-{}:all=", group)
+                        }
+                    })
+                    .collect();
+            }
+        };
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
+
+/// Returns the number of files restored or deleted by a `-restore_original` or
+/// `-delete_original!` command, from its summary (e.g. `1 image files restored from backup`).
+///
+/// Like [`WriteReport::from_output`], an error is only returned when no file was processed.
+pub(crate) fn originals_count(
+    args: &[&str],
+    output: &CommandOutput,
+) -> Result<usize, ExifToolError> {
+    let count = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| {
+            (line.contains("restored") || line.contains("deleted")) && !line.contains("weren't")
+        })
+        .filter_map(|line| line.split_whitespace().next()?.parse::<usize>().ok())
+        .sum();
+
+    if count == 0 {
+        check_stderr(args, &output.stderr)?;
+    }
+    for line in output.stderr.iter().filter(|l| l.starts_with("Error:")) {
+        warn!("ExifTool backup error - {}", line);
+    }
+    Ok(count)
+}
+
+/// Summary of a write command, parsed from the counts `exiftool` prints when it finishes
+/// (e.g. `1 image files updated`).
+///
+/// `exiftool` doesn't print these counts with `-q` (e.g. passed through
+/// [`ExifToolBuilder::common_args`](crate::ExifToolBuilder::common_args)). A write then only
+/// fails on errors reported on stderr, and all counts are zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteReport {
+    /// Number of files that were modified.
+    pub updated: usize,
+    /// Number of files that were processed but left unchanged, e.g. because every tag
+    /// already had the requested value.
+    pub unchanged: usize,
+    /// Number of files that could not be written because of an error
+    /// (`files weren't updated due to errors` or `files weren't created due to errors`).
+    pub failed: usize,
+    /// Number of files skipped because they didn't match the `-if` condition.
+    pub failed_condition: usize,
+    /// Number of new files written, e.g. with `-o` or when creating sidecar files.
+    pub created: usize,
+    /// Warnings reported while writing.
+    pub warnings: Vec<Warning>,
+}
+
+impl WriteReport {
+    /// Builds the report of a write command from its output. `args` is the full command.
+    ///
+    /// Fails if no file was written, found unchanged or skipped by an `-if` condition: with
+    /// the error `exiftool` reported, or with [`ExifToolError::NoFilesUpdated`] if it didn't
+    /// report one (e.g. when none of the tags is writable and it prints `Nothing to do.`).
+    /// Errors for individual files of a command that wrote other files successfully are only
+    /// counted in [`WriteReport::failed`].
+    ///
+    /// Without any summary line (`-q`), only stderr is checked.
+    pub(crate) fn from_output(
+        args: &[&str],
+        output: &CommandOutput,
+    ) -> Result<Self, ExifToolError> {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut report = Self::parse(&stdout);
+        report.warnings = stderr_warnings(args, &output.stderr);
+
+        let quiet = !stdout.lines().any(is_summary_line);
+        let nothing_to_do = output.stderr.iter().any(|l| l.contains("Nothing to do."));
+        if quiet && !nothing_to_do {
+            check_stderr(args, &output.stderr)?;
+            return Ok(report);
+        }
+        if report.updated + report.unchanged + report.created + report.failed_condition == 0 {
+            check_stderr(args, &output.stderr)?;
+            return Err(ExifToolError::NoFilesUpdated {
+                command_args: args.join(" "),
+                std_err: output.stderr.join("\n"),
+            });
+        }
+        for line in output.stderr.iter().filter(|l| l.starts_with("Error:")) {
+            warn!("ExifTool write error - {}", line);
+        }
+        Ok(report)
+    }
+
+    /// Parses the summary lines of a write command's stdout.
+    pub(crate) fn parse(output: &str) -> Self {
+        let mut report = Self::default();
+        for line in output.lines() {
+            let Some((count, message)) = line.trim().split_once(' ') else {
+                continue;
+            };
+            let Ok(count) = count.parse::<usize>() else {
+                continue;
+            };
+            match message {
+                "image files updated" => report.updated += count,
+                "image files unchanged" => report.unchanged += count,
+                "image files created" => report.created += count,
+                "files weren't updated due to errors" | "files weren't created due to errors" => {
+                    report.failed += count
+                }
+                "files failed condition" => report.failed_condition += count,
+                _ => {}
+            }
+        }
+        report
+    }
+}
+
+/// Whether `line` is one of the counts `exiftool` prints after a command, like
+/// `1 image files updated`.
+fn is_summary_line(line: &str) -> bool {
+    line.trim()
+        .split_once(' ')
+        .is_some_and(|(count, _)| count.parse::<usize>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_args() {
+        let writes = WriteSet::new()
+            .set("XMP-dc:Title", "Sunset")
+            .set("ISO", 200)
+            .append("Keywords", "beach")
+            .remove("Keywords", "draft")
+            .delete("GPS:all")
+            .copy("XMP:CreateDate", "EXIF:DateTimeOriginal");
+        assert_eq!(writes.len(), 6);
+        assert_eq!(
+            writes.to_args(),
+            [
+                "-XMP-dc:Title=Sunset",
+                "-ISO=200",
+                "-Keywords+=beach",
+                "-Keywords-=draft",
+                "-GPS:all=",
+                "-XMP:CreateDate<EXIF:DateTimeOriginal",
+            ]
+        );
+        assert!(WriteSet::new().is_empty());
+    }
+
+    fn map(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_copy_selection_args() {
+        assert!(CopySelection::all().to_args().is_empty());
+        assert_eq!(
+            CopySelection::all().exclude("MakerNotes:all").to_args(),
+            ["--MakerNotes:all"]
+        );
+        assert_eq!(
+            CopySelection::all()
+                .tags(["Artist", "Copyright"])
+                .redirect("XMP:CreateDate", "EXIF:DateTimeOriginal")
+                .to_args(),
+            [
+                "-Artist",
+                "-Copyright",
+                "-XMP:CreateDate<EXIF:DateTimeOriginal"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_policy_args() {
+        assert_eq!(StripPolicy::All.to_args(), ["-all="]);
+        assert_eq!(
+            StripPolicy::KeepColorProfile.to_args(),
+            ["-all=", "-tagsFromFile", "@", "-ICC_Profile"]
+        );
+        assert_eq!(
+            StripPolicy::PrivacyOnly.to_args(),
+            [
+                "-GPS:all=",
+                "-GPS*=",
+                "-*SerialNumber=",
+                "-*OwnerName=",
+                "-MakerNotes:all="
+            ]
+        );
+        // Plain names are groups; a `:` or `*` makes the entry a tag name.
+        assert_eq!(
+            StripPolicy::Custom(vec![
+                "XMP".to_string(),
+                "EXIF:Artist".to_string(),
+                "*Serial*".to_string(),
+                "XMP-exif:GPS*".to_string(),
+            ])
+            .to_args(),
+            [
+                "-XMP:all=",
+                "-EXIF:Artist=",
+                "-*Serial*=",
+                "-XMP-exif:GPS*="
+            ]
+        );
+        assert!(StripPolicy::Custom(Vec::new()).to_args().is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let current = map(json!({
+            "SourceFile": "a.jpg",
+            "EXIF:IFD0:Artist": "Old Artist",
+            "IPTC:IPTC:Keywords": ["beach", "draft"],
+            "EXIF:IFD0:Make": "Huawei",
+            "EXIF:ExifIFD:ISO": 100,
+        }));
+        let writes = WriteSet::new()
+            .set("EXIF:Artist", "New Artist")
+            .append("Keywords", "sea")
+            .remove("Keywords", "draft")
+            .set("ISO", 100)
+            .delete("Comment")
+            .copy("XMP:Title", "Make");
+
+        assert_eq!(
+            writes.read_args(),
+            [
+                "-a",
+                "-G0:1",
+                "-EXIF:Artist",
+                "-Keywords",
+                "-ISO",
+                "-Comment",
+                "-XMP:Title",
+                "-Make"
+            ]
+        );
+        assert_eq!(
+            writes.diff(&current),
+            vec![
+                TagChange {
+                    tag: "EXIF:Artist".to_string(),
+                    old: Some(json!("Old Artist")),
+                    new: Some(json!("New Artist")),
+                },
+                TagChange {
+                    tag: "Keywords".to_string(),
+                    old: Some(json!(["beach", "draft"])),
+                    new: Some(json!(["beach", "sea"])),
+                },
+                TagChange {
+                    tag: "XMP:Title".to_string(),
+                    old: None,
+                    new: Some(json!("Huawei")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_same_tag_in_different_groups() {
+        let current = map(json!({
+            "EXIF:IFD0:Artist": "Exif Artist",
+            "XMP:XMP-dc:Creator": "Xmp Creator",
+            "XMP:XMP-tiff:Artist": "Xmp Artist",
+        }));
+        let writes = WriteSet::new()
+            .set("EXIF:Artist", "New")
+            .set("XMP-tiff:Artist", "New")
+            .copy("IPTC:By-line", "XMP:Artist");
+
+        assert_eq!(
+            writes.diff(&current),
+            vec![
+                TagChange {
+                    tag: "EXIF:Artist".to_string(),
+                    old: Some(json!("Exif Artist")),
+                    new: Some(json!("New")),
+                },
+                TagChange {
+                    tag: "XMP-tiff:Artist".to_string(),
+                    old: Some(json!("Xmp Artist")),
+                    new: Some(json!("New")),
+                },
+                TagChange {
+                    tag: "IPTC:By-line".to_string(),
+                    old: None,
+                    new: Some(json!("Xmp Artist")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_repeated_set_builds_list() {
+        let current = map(json!({ "IPTC:IPTC:Keywords": "old" }));
+        let writes = WriteSet::new().set("Keywords", "a").set("Keywords", "b");
+        assert_eq!(
+            writes.diff(&current),
+            vec![TagChange {
+                tag: "Keywords".to_string(),
+                old: Some(json!("old")),
+                new: Some(json!(["a", "b"])),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_group_delete() {
+        let writes = WriteSet::new().delete("GPS:all").delete("XMP:Title");
+        assert_eq!(
+            writes.read_args(),
+            ["-a", "-G0:1", "-GPS:all", "-XMP:Title"]
+        );
+
+        let current = map(json!({
+            "SourceFile": "a.jpg",
+            "File:System:FileName": "a.jpg",
+            "EXIF:GPS:GPSLatitude": "52 deg 22' 12.00\" N",
+            "XMP:XMP-dc:Title": "hello",
+        }));
+        assert_eq!(
+            writes.diff(&current),
+            vec![
+                TagChange {
+                    tag: "GPS:GPSLatitude".to_string(),
+                    old: Some(json!("52 deg 22' 12.00\" N")),
+                    new: None,
+                },
+                TagChange {
+                    tag: "XMP:Title".to_string(),
+                    old: Some(json!("hello")),
+                    new: None,
+                },
+            ]
+        );
+
+        let everything = WriteSet::new().delete("all");
+        assert_eq!(
+            everything
+                .diff(&current)
+                .into_iter()
+                .map(|change| change.tag)
+                .collect::<Vec<_>>(),
+            ["GPS:GPSLatitude", "XMP-dc:Title"]
+        );
+    }
+
+    #[test]
+    fn test_diff_group_delete_merges_with_tags() {
+        let current = map(json!({
+            "EXIF:IFD0:Artist": "Old",
+            "EXIF:IFD0:Make": "Huawei",
+            "File:System:FileName": "a.jpg",
+            "XMP:XMP-dc:Subject": ["a", "b"],
+        }));
+        let changed = |tag: &str, old: Value, new: Option<Value>| TagChange {
+            tag: tag.to_string(),
+            old: Some(old),
+            new,
+        };
+
+        // Strip then set: the set updates the deleted entry and starts from nothing.
+        let writes = WriteSet::new()
+            .delete("all")
+            .set("Artist", "x")
+            .append("Subject", "c");
+        assert_eq!(
+            writes.diff(&current),
+            vec![
+                changed("IFD0:Artist", json!("Old"), Some(json!("x"))),
+                changed("IFD0:Make", json!("Huawei"), None),
+                changed("XMP-dc:Subject", json!(["a", "b"]), Some(json!(["c"]))),
+            ]
+        );
+
+        // Set then strip: the group deletion overrides earlier assignments.
+        let writes = WriteSet::new()
+            .set("Artist", "x")
+            .set("XMP:Title", "t")
+            .delete("all");
+        assert_eq!(
+            writes.diff(&current),
+            vec![
+                changed("Artist", json!("Old"), None),
+                changed("IFD0:Make", json!("Huawei"), None),
+                changed("XMP-dc:Subject", json!(["a", "b"]), None),
+            ]
+        );
+
+        // Assignments outside the deleted group are kept.
+        let writes = WriteSet::new()
+            .set("Artist", "x")
+            .set("XMP:Title", "t")
+            .delete("EXIF:all");
+        assert_eq!(
+            writes.diff(&current),
+            vec![
+                changed("Artist", json!("Old"), None),
+                TagChange {
+                    tag: "XMP:Title".to_string(),
+                    old: None,
+                    new: Some(json!("t")),
+                },
+                changed("IFD0:Make", json!("Huawei"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backup_policy_arg() {
+        assert_eq!(BackupPolicy::default(), BackupPolicy::KeepOriginal);
+        assert_eq!(BackupPolicy::KeepOriginal.arg(), None);
+        assert_eq!(
+            BackupPolicy::OverwriteInPlace.arg(),
+            Some("-overwrite_original_in_place")
+        );
+        assert_eq!(
+            BackupPolicy::OverwriteOriginal.arg(),
+            Some("-overwrite_original")
+        );
+    }
+
+    #[test]
+    fn test_originals_count() {
+        let output = CommandOutput {
+            stdout: b"    2 image files restored from backup\n    1 files weren't restored due to errors\n"
+                .to_vec(),
+            stderr: vec!["Error: File not found - c.jpg".to_string()],
+        };
+        assert_eq!(originals_count(&["-restore_original"], &output).unwrap(), 2);
+
+        let output = CommandOutput {
+            stdout: b"    1 original files deleted\n".to_vec(),
+            stderr: Vec::new(),
+        };
+        assert_eq!(originals_count(&["-delete_original!"], &output).unwrap(), 1);
+
+        let output = CommandOutput {
+            stdout: Vec::new(),
+            stderr: vec!["Error: File not found - c.jpg".to_string()],
+        };
+        assert_matches!(
+            originals_count(&["-restore_original", "c.jpg"], &output),
+            Err(ExifToolError::FileNotFound { .. })
+        );
+    }
+
+    #[test]
+    fn test_parse_report() {
+        let output = "    1 directories scanned\n    3 image files updated\n    2 image files unchanged\n    1 image files created\n    4 files weren't updated due to errors\n";
+        assert_eq!(
+            WriteReport::parse(output),
+            WriteReport {
+                updated: 3,
+                unchanged: 2,
+                failed: 4,
+                failed_condition: 0,
+                created: 1,
+                warnings: vec![],
+            }
+        );
+        assert_eq!(WriteReport::parse(""), WriteReport::default());
+    }
+
+    #[test]
+    fn test_parse_report_created_and_condition() {
+        let output = "    2 image files created\n    1 files weren't created due to errors\n";
+        let report = WriteReport::parse(output);
+        assert_eq!((report.created, report.failed), (2, 1));
+
+        let output = "    3 files failed condition\n    1 image files updated\n";
+        let report = WriteReport::parse(output);
+        assert_eq!((report.updated, report.failed_condition), (1, 3));
+    }
+
+    #[test]
+    fn test_from_output_condition_only() -> Result<(), ExifToolError> {
+        let args = ["-if", "$Make eq 'Canon'", "-Title=x", "a.jpg"];
+        let output = CommandOutput {
+            stdout: b"    1 files failed condition\n".to_vec(),
+            stderr: vec![],
+        };
+        let report = WriteReport::from_output(&args, &output)?;
+        assert_eq!(report.failed_condition, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_output_quiet() {
+        let args = ["-q", "-Title=x", "a.jpg"];
+        let written = CommandOutput::default();
+        assert_eq!(
+            WriteReport::from_output(&args, &written).ok(),
+            Some(WriteReport::default())
+        );
+
+        let failed = CommandOutput {
+            stdout: vec![],
+            stderr: vec!["Error: File not found - a.jpg".to_string()],
+        };
+        assert_matches!(
+            WriteReport::from_output(&args, &failed),
+            Err(ExifToolError::FileNotFound { .. })
+        );
+    }
+
+    #[test]
+    fn test_from_output_partial_failure() -> Result<(), ExifToolError> {
+        let args = ["-Title=x", "a.jpg", "missing.jpg"];
+        let output = CommandOutput {
+            stdout: b"    1 image files updated\n    1 files weren't updated due to errors\n"
+                .to_vec(),
+            stderr: vec![
+                "Warning: [minor] Bad MakerNotes offset - a.jpg".to_string(),
+                "Error: File not found - missing.jpg".to_string(),
+            ],
+        };
+        let report = WriteReport::from_output(&args, &output)?;
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].file, Some("a.jpg".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_output_nothing_written() {
+        let args = ["-NonExistentTag=x", "a.jpg"];
+        let output = CommandOutput {
+            stdout: vec![],
+            stderr: vec![
+                "Warning: Tag 'NonExistentTag' is not defined".to_string(),
+                "Nothing to do.".to_string(),
+            ],
+        };
+        let result = WriteReport::from_output(&args, &output);
+        assert_matches!(result, Err(ExifToolError::NoFilesUpdated { .. }));
+
+        let output = CommandOutput {
+            stdout: b"    0 image files updated\n    1 files weren't updated due to errors\n"
+                .to_vec(),
+            stderr: vec!["Error: File not found - a.jpg".to_string()],
+        };
+        let result = WriteReport::from_output(&args, &output);
+        assert_matches!(result, Err(ExifToolError::FileNotFound { .. }));
+    }
+}